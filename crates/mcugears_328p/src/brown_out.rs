@@ -0,0 +1,114 @@
+// 電源電圧を監視するBOD(Brown-out Detector)
+// 検出電圧-ヒステリシス/2を下回るとリセットし,検出電圧+ヒステリシス/2を上回ると解除する
+// 要素import
+use crate::fuses::BrownOutLevel;
+use crate::supply::SupplyVoltage;
+
+// BODのヒステリシス(mV)
+pub const BROWN_OUT_HYSTERESIS_MILLIVOLTS: u32 = 50;
+
+// BOD
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BrownOutDetector {
+    level: BrownOutLevel,
+    in_reset: bool,
+}
+
+impl BrownOutDetector {
+    // 初期化
+    pub fn new(level: BrownOutLevel) -> Self {
+        BrownOutDetector {
+            level,
+            in_reset: false,
+        }
+    }
+
+    // 電源電圧を与えてリセット中かどうかを返す
+    pub fn update(&mut self, supply_millivolts: u32) -> bool {
+        let Some(threshold) = self.level.threshold_millivolts() else {
+            return false;
+        };
+
+        let half = BROWN_OUT_HYSTERESIS_MILLIVOLTS / 2;
+        self.in_reset = match self.in_reset {
+            true => supply_millivolts <= threshold + half,
+            false => supply_millivolts < threshold - half,
+        };
+
+        self.in_reset
+    }
+
+    // 電源電圧の推移に従ってstepでの状態に更新
+    pub fn follow(&mut self, supply: &SupplyVoltage, step: u64) -> bool {
+        self.update(supply.at(step))
+    }
+
+    // リセット中かどうか
+    pub fn in_reset(&self) -> bool {
+        self.in_reset
+    }
+}
+
+#[cfg(test)]
+mod brown_out_tests {
+    use super::*;
+    use crate::fuses::Fuses;
+    use rstest::rstest;
+
+    // 電圧ごとの更新
+    #[cfg(test)]
+    mod update {
+        use super::*;
+
+        // 電圧の推移とリセット状態(2.7V,ヒステリシス2675-2725mV)
+        #[rstest]
+        #[case::stable(&[5000, 3300, 2700], false)]
+        #[case::drop(&[3300, 2674], true)]
+        #[case::within_hysteresis(&[3300, 2600, 2725], true)]
+        #[case::recover(&[3300, 2600, 2726], false)]
+        #[case::no_trigger_within_hysteresis(&[3300, 2675], false)]
+        fn supply_sequence(#[case] supply: &[u32], #[case] expected: bool) {
+            let mut detector = BrownOutDetector::new(Fuses::ARDUINO_UNO.brown_out_level());
+
+            for millivolts in supply {
+                detector.update(*millivolts);
+            }
+
+            assert_eq!(detector.in_reset(), expected);
+        }
+
+        // 無効ならリセットしない
+        #[test]
+        fn disabled() {
+            let mut detector = BrownOutDetector::new(Fuses::default().brown_out_level());
+
+            assert!(!detector.update(0));
+        }
+    }
+
+    // 電源電圧の推移に従う
+    #[cfg(test)]
+    mod follow {
+        use super::*;
+
+        // 電池の消耗(3.3Vから1000ステップで2.4V)と交換(2000で3.3V)
+        // 2675mVを下回るのは696ステップ目以降
+        #[rstest]
+        #[case::before_drop(695, false)]
+        #[case::dropped(696, true)]
+        #[case::drained(1999, true)]
+        #[case::replaced(2000, false)]
+        fn battery_discharge(#[case] until: u64, #[case] expected: bool) {
+            let supply = SupplyVoltage::constant(3300)
+                .ramp_to(1000, 2400)
+                .step_to(2000, 3300);
+            let mut detector = BrownOutDetector::new(Fuses::ARDUINO_UNO.brown_out_level());
+
+            for step in 0..=until {
+                detector.follow(&supply, step);
+            }
+
+            assert_eq!(detector.in_reset(), expected);
+        }
+    }
+}
@@ -0,0 +1,413 @@
+// ATmega328Pのヒューズビットとロックビット
+// データシート「28. Memory Programming」に従う
+// ビットは0でプログラム済み(有効),1で未プログラム(無効)
+// アドレスは全てワード単位
+
+// フラッシュのワード数(32KB)
+pub const FLASH_WORDS: usize = 0x4000;
+
+// ヒューズビット
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fuses {
+    pub low: u8,
+    pub high: u8,
+    pub extended: u8,
+}
+
+// 出荷時の値
+impl Default for Fuses {
+    fn default() -> Self {
+        Fuses {
+            low: 0x62,
+            high: 0xD9,
+            extended: 0xFF,
+        }
+    }
+}
+
+impl Fuses {
+    // Arduino Unoの値
+    pub const ARDUINO_UNO: Fuses = Fuses {
+        low: 0xFF,
+        high: 0xDE,
+        extended: 0xFD,
+    };
+
+    // クロック源(CKSEL3:0)
+    pub fn clock_source(&self) -> ClockSource {
+        match self.low & 0x0F {
+            0b0000 => ClockSource::ExternalClock,
+            0b0010 => ClockSource::InternalRc8MHz,
+            0b0011 => ClockSource::InternalRc128kHz,
+            0b0100 | 0b0101 => ClockSource::LowFrequencyCrystal,
+            0b0110 | 0b0111 => ClockSource::FullSwingCrystal,
+            0b1000..=0b1111 => ClockSource::LowPowerCrystal,
+            _ => ClockSource::Reserved,
+        }
+    }
+
+    // クロックを8分周するか(CKDIV8)
+    pub fn clock_divided_by_8(&self) -> bool {
+        is_programmed(self.low, 7)
+    }
+
+    // BOD検出レベル(BODLEVEL2:0)
+    pub fn brown_out_level(&self) -> BrownOutLevel {
+        match self.extended & 0b111 {
+            0b111 => BrownOutLevel::Disabled,
+            0b110 => BrownOutLevel::V1_8,
+            0b101 => BrownOutLevel::V2_7,
+            0b100 => BrownOutLevel::V4_3,
+            _ => BrownOutLevel::Reserved,
+        }
+    }
+
+    // チップ消去時にEEPROMを保持するか(EESAVE)
+    pub fn eeprom_preserved(&self) -> bool {
+        is_programmed(self.high, 3)
+    }
+
+    // ブートセクションのワード数(BOOTSZ1:0)
+    pub fn boot_size(&self) -> usize {
+        match (self.high >> 1) & 0b11 {
+            0b11 => 256,
+            0b10 => 512,
+            0b01 => 1024,
+            _ => 2048,
+        }
+    }
+
+    // ブートセクションの開始アドレス
+    pub fn boot_start(&self) -> usize {
+        FLASH_WORDS - self.boot_size()
+    }
+
+    // アドレスがブートセクションかどうか
+    pub fn in_boot_section(&self, address: usize) -> bool {
+        (self.boot_start()..FLASH_WORDS).contains(&address)
+    }
+
+    // リセットベクタ(BOOTRSTが有効ならブートセクションの先頭)
+    pub fn reset_vector(&self) -> usize {
+        match is_programmed(self.high, 0) {
+            true => self.boot_start(),
+            false => 0x0000,
+        }
+    }
+}
+
+// ロックビット
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LockBits(pub u8);
+
+// 出荷時の値(ロック無し)
+impl Default for LockBits {
+    fn default() -> Self {
+        LockBits(0xFF)
+    }
+}
+
+impl LockBits {
+    // 外部からの書き込み,読み出しの禁止(LB2:1)
+    pub fn memory_lock(&self) -> MemoryLock {
+        match self.0 & 0b11 {
+            0b11 => MemoryLock::Unlocked,
+            0b10 => MemoryLock::ProgrammingDisabled,
+            0b00 => MemoryLock::ProgrammingAndVerificationDisabled,
+            _ => MemoryLock::Reserved,
+        }
+    }
+
+    // アプリケーションセクションの保護(BLB02:01)
+    pub fn application_protection(&self) -> BootLockMode {
+        BootLockMode::from_bits(self.0 >> 2)
+    }
+
+    // ブートセクションの保護(BLB12:11)
+    pub fn boot_protection(&self) -> BootLockMode {
+        BootLockMode::from_bits(self.0 >> 4)
+    }
+
+    // SPMでの書き込みが許可されるか
+    // SPMはブートセクションで実行した場合のみ有効
+    pub fn allows_spm_write(&self, fuses: &Fuses, executing_from: usize, target: usize) -> bool {
+        if !fuses.in_boot_section(executing_from) {
+            return false;
+        }
+
+        match fuses.in_boot_section(target) {
+            true => self.boot_protection().allows_spm_write(),
+            false => self.application_protection().allows_spm_write(),
+        }
+    }
+
+    // LPMでの読み出しが許可されるか
+    // 同じセクション内の読み出しは常に許可される
+    pub fn allows_lpm_read(&self, fuses: &Fuses, executing_from: usize, target: usize) -> bool {
+        match (
+            fuses.in_boot_section(executing_from),
+            fuses.in_boot_section(target),
+        ) {
+            (true, false) => self
+                .application_protection()
+                .allows_lpm_from_other_section(),
+            (false, true) => self.boot_protection().allows_lpm_from_other_section(),
+            _ => true,
+        }
+    }
+}
+
+// ビットがプログラム済み(0)かどうか
+fn is_programmed(byte: u8, bit: u8) -> bool {
+    byte & (1 << bit) == 0
+}
+
+// クロック源
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClockSource {
+    ExternalClock,
+    InternalRc8MHz,
+    InternalRc128kHz,
+    LowFrequencyCrystal,
+    FullSwingCrystal,
+    LowPowerCrystal,
+    Reserved,
+}
+
+// BOD検出レベル
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BrownOutLevel {
+    Disabled,
+    V1_8,
+    V2_7,
+    V4_3,
+    Reserved,
+}
+
+impl BrownOutLevel {
+    // 検出電圧の標準値(mV)
+    pub fn threshold_millivolts(self) -> Option<u32> {
+        match self {
+            BrownOutLevel::V1_8 => Some(1800),
+            BrownOutLevel::V2_7 => Some(2700),
+            BrownOutLevel::V4_3 => Some(4300),
+            BrownOutLevel::Disabled | BrownOutLevel::Reserved => None,
+        }
+    }
+}
+
+// メモリロックのモード
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MemoryLock {
+    Unlocked,
+    ProgrammingDisabled,
+    ProgrammingAndVerificationDisabled,
+    Reserved,
+}
+
+// ブートロックのモード(データシートのBLB mode 1-4)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BootLockMode {
+    // 制限なし
+    Unrestricted,
+    // SPMでの書き込み禁止
+    SpmWriteDisabled,
+    // SPMでの書き込み禁止,他セクションからのLPM禁止
+    SpmWriteAndLpmDisabled,
+    // 他セクションからのLPM禁止
+    LpmDisabled,
+}
+
+impl BootLockMode {
+    // 下位2ビットから変換
+    fn from_bits(bits: u8) -> Self {
+        match bits & 0b11 {
+            0b11 => BootLockMode::Unrestricted,
+            0b10 => BootLockMode::SpmWriteDisabled,
+            0b00 => BootLockMode::SpmWriteAndLpmDisabled,
+            _ => BootLockMode::LpmDisabled,
+        }
+    }
+
+    // SPMでの書き込みが許可されるか
+    fn allows_spm_write(self) -> bool {
+        matches!(self, BootLockMode::Unrestricted | BootLockMode::LpmDisabled)
+    }
+
+    // 他セクションからのLPMが許可されるか
+    fn allows_lpm_from_other_section(self) -> bool {
+        matches!(
+            self,
+            BootLockMode::Unrestricted | BootLockMode::SpmWriteDisabled
+        )
+    }
+}
+
+#[cfg(test)]
+mod fuses_tests {
+    use super::*;
+    use rstest::rstest;
+
+    // ヒューズの解釈
+    #[cfg(test)]
+    mod fuses {
+        use super::*;
+
+        // 出荷時の値
+        #[test]
+        fn factory_default() {
+            let fuses = Fuses::default();
+
+            assert_eq!(fuses.clock_source(), ClockSource::InternalRc8MHz);
+            assert!(fuses.clock_divided_by_8());
+            assert_eq!(fuses.brown_out_level(), BrownOutLevel::Disabled);
+            assert!(!fuses.eeprom_preserved());
+            assert_eq!(fuses.boot_size(), 2048);
+            assert_eq!(fuses.reset_vector(), 0x0000);
+        }
+
+        // Arduino Unoの値(optibootへリセット)
+        #[test]
+        fn arduino_uno() {
+            let fuses = Fuses::ARDUINO_UNO;
+
+            assert_eq!(fuses.clock_source(), ClockSource::LowPowerCrystal);
+            assert!(!fuses.clock_divided_by_8());
+            assert_eq!(fuses.brown_out_level(), BrownOutLevel::V2_7);
+            assert_eq!(fuses.boot_size(), 256);
+            assert_eq!(fuses.reset_vector(), 0x3F00);
+        }
+
+        // ブートセクションの大きさとリセットベクタ
+        #[rstest]
+        #[case::size_256(0b1101_1110, 0x3F00)]
+        #[case::size_512(0b1101_1100, 0x3E00)]
+        #[case::size_1024(0b1101_1010, 0x3C00)]
+        #[case::size_2048(0b1101_1000, 0x3800)]
+        #[case::application_reset(0b1101_1001, 0x0000)]
+        fn reset_vector(#[case] high: u8, #[case] expected: usize) {
+            let fuses = Fuses {
+                high,
+                ..Default::default()
+            };
+
+            assert_eq!(fuses.reset_vector(), expected);
+        }
+
+        // クロック源
+        #[rstest]
+        #[case::external(0b0000, ClockSource::ExternalClock)]
+        #[case::reserved(0b0001, ClockSource::Reserved)]
+        #[case::internal_128khz(0b0011, ClockSource::InternalRc128kHz)]
+        #[case::low_frequency(0b0101, ClockSource::LowFrequencyCrystal)]
+        #[case::full_swing(0b0111, ClockSource::FullSwingCrystal)]
+        #[case::low_power(0b1111, ClockSource::LowPowerCrystal)]
+        fn clock_source(#[case] cksel: u8, #[case] expected: ClockSource) {
+            let fuses = Fuses {
+                low: 0xF0 | cksel,
+                ..Default::default()
+            };
+
+            assert_eq!(fuses.clock_source(), expected);
+        }
+
+        // EEPROMの保持
+        #[test]
+        fn eeprom_preserved() {
+            let fuses = Fuses {
+                high: 0xD1,
+                ..Default::default()
+            };
+
+            assert!(fuses.eeprom_preserved());
+        }
+    }
+
+    // ロックビットの解釈
+    #[cfg(test)]
+    mod lock_bits {
+        use super::*;
+
+        // ブートセクション(0x3800-)とアプリケーションセクションのアドレス
+        const BOOT: usize = 0x3900;
+        const APPLICATION: usize = 0x0100;
+
+        // ロック無し
+        #[test]
+        fn unlocked() {
+            let (fuses, lock_bits) = (Fuses::default(), LockBits::default());
+
+            assert_eq!(lock_bits.memory_lock(), MemoryLock::Unlocked);
+            assert!(lock_bits.allows_spm_write(&fuses, BOOT, APPLICATION));
+            assert!(lock_bits.allows_spm_write(&fuses, BOOT, BOOT));
+            assert!(lock_bits.allows_lpm_read(&fuses, BOOT, APPLICATION));
+            assert!(lock_bits.allows_lpm_read(&fuses, APPLICATION, BOOT));
+        }
+
+        // アプリケーションセクションからのSPMは無効
+        #[test]
+        fn spm_from_application() {
+            let (fuses, lock_bits) = (Fuses::default(), LockBits::default());
+
+            assert!(!lock_bits.allows_spm_write(&fuses, APPLICATION, APPLICATION));
+        }
+
+        // メモリロック
+        #[rstest]
+        #[case::mode2(0xFE, MemoryLock::ProgrammingDisabled)]
+        #[case::mode3(0xFC, MemoryLock::ProgrammingAndVerificationDisabled)]
+        fn memory_lock(#[case] bits: u8, #[case] expected: MemoryLock) {
+            assert_eq!(LockBits(bits).memory_lock(), expected);
+        }
+
+        // アプリケーションセクションの保護(BLB0)
+        #[rstest]
+        #[case::mode1(0b11, true, true)]
+        #[case::mode2(0b10, false, true)]
+        #[case::mode3(0b00, false, false)]
+        #[case::mode4(0b01, true, false)]
+        fn application_protection(
+            #[case] blb0: u8,
+            #[case] spm_write: bool,
+            #[case] lpm_from_boot: bool,
+        ) {
+            let fuses = Fuses::default();
+            let lock_bits = LockBits(0xF3 | (blb0 << 2));
+
+            assert_eq!(
+                lock_bits.allows_spm_write(&fuses, BOOT, APPLICATION),
+                spm_write
+            );
+            assert_eq!(
+                lock_bits.allows_lpm_read(&fuses, BOOT, APPLICATION),
+                lpm_from_boot
+            );
+            // ブートセクションには影響しない
+            assert!(lock_bits.allows_spm_write(&fuses, BOOT, BOOT));
+            assert!(lock_bits.allows_lpm_read(&fuses, APPLICATION, APPLICATION));
+        }
+
+        // ブートセクションの保護(BLB1)
+        #[rstest]
+        #[case::mode1(0b11, true, true)]
+        #[case::mode2(0b10, false, true)]
+        #[case::mode3(0b00, false, false)]
+        #[case::mode4(0b01, true, false)]
+        fn boot_protection(
+            #[case] blb1: u8,
+            #[case] spm_write: bool,
+            #[case] lpm_from_application: bool,
+        ) {
+            let fuses = Fuses::default();
+            let lock_bits = LockBits(0xCF | (blb1 << 4));
+
+            assert_eq!(lock_bits.allows_spm_write(&fuses, BOOT, BOOT), spm_write);
+            assert_eq!(
+                lock_bits.allows_lpm_read(&fuses, APPLICATION, BOOT),
+                lpm_from_application
+            );
+            // アプリケーションセクションには影響しない
+            assert!(lock_bits.allows_spm_write(&fuses, BOOT, APPLICATION));
+        }
+    }
+}
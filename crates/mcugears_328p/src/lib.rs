@@ -1,3 +1,8 @@
+// 要素import
+pub mod brown_out;
+pub mod fuses;
+pub mod supply;
+
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
 }
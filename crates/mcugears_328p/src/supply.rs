@@ -0,0 +1,190 @@
+// 電源電圧の推移
+// ステップ(サイクル,命令数など呼び出し側が決める単位)ごとの電圧を折れ線で表す
+// VCCを基準電圧とするADCの変換値もここで求める
+//
+//     // 5Vから1000ステップで2.5Vまで低下し,その後3.3Vに復帰
+//     let supply = SupplyVoltage::constant(5000)
+//         .ramp_to(1000, 2500)
+//         .step_to(1500, 3300);
+
+// 電源電圧
+#[derive(Clone, Debug, PartialEq)]
+pub struct SupplyVoltage {
+    // (ステップ, mV)をステップ順に保持
+    points: Vec<(u64, u32)>,
+}
+
+impl SupplyVoltage {
+    // 一定の電圧
+    pub fn constant(millivolts: u32) -> Self {
+        SupplyVoltage {
+            points: vec![(0, millivolts)],
+        }
+    }
+
+    // 直前の点から直線的に変化
+    pub fn ramp_to(mut self, step: u64, millivolts: u32) -> Self {
+        self.check_step(step);
+        self.points.push((step, millivolts));
+        self
+    }
+
+    // 直前の電圧を保ち,stepで切り替わる
+    pub fn step_to(mut self, step: u64, millivolts: u32) -> Self {
+        self.check_step(step);
+        let (_, previous) = self.last();
+        self.points.push((step - 1, previous));
+        self.points.push((step, millivolts));
+        self
+    }
+
+    // stepでの電圧(最後の点以降はその電圧を保つ)
+    pub fn at(&self, step: u64) -> u32 {
+        let next = self.points.partition_point(|(point, _)| *point <= step);
+        if next == self.points.len() {
+            return self.last().1;
+        }
+
+        // 前後の点で補間(長い区間でも溢れないようにu128で計算)
+        let (start, from) = self.points[next - 1];
+        let (end, to) = self.points[next];
+        let elapsed = u128::from(step - start);
+        let length = u128::from(end - start);
+        let delta = (u128::from(from.abs_diff(to)) * elapsed / length) as u32;
+        match to >= from {
+            true => from + delta,
+            false => from - delta,
+        }
+    }
+
+    // stepでのADC変換値(VCCを基準電圧とする)
+    pub fn adc_reading_at(&self, input_millivolts: u32, step: u64) -> u16 {
+        adc_reading(input_millivolts, self.at(step))
+    }
+
+    // 最後の点
+    fn last(&self) -> (u64, u32) {
+        *self
+            .points
+            .last()
+            .expect("supply voltage has an initial point")
+    }
+
+    // ステップが増加しているか確認
+    fn check_step(&self, step: u64) {
+        let (previous, _) = self.last();
+        assert!(
+            step > previous,
+            "supply voltage step {step} must come after step {previous}"
+        );
+    }
+}
+
+// 内部基準電圧(バンドギャップ)の標準値(mV)
+// ADMUXでMUX3:0=1110を選ぶとVCC基準で読める(電池電圧の監視に使う)
+pub const BANDGAP_MILLIVOLTS: u32 = 1100;
+
+// ADC変換値(10bit)
+// 入力電圧*1024/基準電圧を1023で飽和させる(基準電圧が0の場合は0)
+pub fn adc_reading(input_millivolts: u32, reference_millivolts: u32) -> u16 {
+    if reference_millivolts == 0 {
+        return 0;
+    }
+
+    let reading = u64::from(input_millivolts) * 1024 / u64::from(reference_millivolts);
+    reading.min(1023) as u16
+}
+
+#[cfg(test)]
+mod supply_tests {
+    use super::*;
+    use rstest::rstest;
+
+    // 電圧の推移
+    #[cfg(test)]
+    mod at {
+        use super::*;
+
+        // 5V一定 -> 1000で2.5V(直線) -> 1500で3.3V(切り替え)
+        #[rstest]
+        #[case::start(0, 5000)]
+        #[case::ramp_middle(500, 3750)]
+        #[case::ramp_end(1000, 2500)]
+        #[case::hold(1499, 2500)]
+        #[case::switched(1500, 3300)]
+        #[case::after_last(100_000, 3300)]
+        fn voltage(#[case] step: u64, #[case] expected: u32) {
+            let supply = SupplyVoltage::constant(5000)
+                .ramp_to(1000, 2500)
+                .step_to(1500, 3300);
+
+            assert_eq!(supply.at(step), expected);
+        }
+
+        // 上昇も補間する
+        #[test]
+        fn rising_ramp() {
+            let supply = SupplyVoltage::constant(0).ramp_to(100, 3300);
+
+            assert_eq!(supply.at(10), 330);
+        }
+
+        // 非常に長い区間でも溢れない
+        #[rstest]
+        #[case::falling(5000, 0, 2500)]
+        #[case::rising(0, 5000, 2500)]
+        fn long_ramp(#[case] from: u32, #[case] to: u32, #[case] expected: u32) {
+            let supply = SupplyVoltage::constant(from).ramp_to(u64::MAX, to);
+
+            assert_eq!(supply.at(u64::MAX / 2 + 1), expected);
+            assert_eq!(supply.at(u64::MAX), to);
+        }
+
+        // ステップが戻る指定はpanic
+        #[test]
+        #[should_panic(expected = "step 100 must come after step 100")]
+        fn non_increasing_step() {
+            let _ = SupplyVoltage::constant(5000)
+                .ramp_to(100, 3300)
+                .ramp_to(100, 2500);
+        }
+    }
+
+    // VCC基準のADC変換
+    #[cfg(test)]
+    mod adc {
+        use super::*;
+
+        // 変換値
+        #[rstest]
+        #[case::zero(0, 5000, 0)]
+        #[case::half(2500, 5000, 512)]
+        #[case::just_below_reference(4996, 5000, 1023)]
+        #[case::above_reference(6000, 5000, 1023)]
+        #[case::no_reference(1000, 0, 0)]
+        fn reading(#[case] input: u32, #[case] reference: u32, #[case] expected: u16) {
+            assert_eq!(adc_reading(input, reference), expected);
+        }
+
+        // 電池の消耗でバンドギャップの変換値が上がる
+        // 5V: 1100*1024/5000=225, 3V: 1100*1024/3000=375
+        #[rstest]
+        #[case::full(0, 225)]
+        #[case::half_drained(500, 281)]
+        #[case::drained(1000, 375)]
+        fn battery_monitor(#[case] step: u64, #[case] expected: u16) {
+            let supply = SupplyVoltage::constant(5000).ramp_to(1000, 3000);
+
+            assert_eq!(supply.adc_reading_at(BANDGAP_MILLIVOLTS, step), expected);
+        }
+
+        // 入力電圧が一定でもVCCが下がると変換値が上がる
+        #[test]
+        fn sagging_reference() {
+            let supply = SupplyVoltage::constant(5000).step_to(100, 4000);
+
+            assert_eq!(supply.adc_reading_at(2000, 99), 409);
+            assert_eq!(supply.adc_reading_at(2000, 100), 512);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+// 要素import
+use crate::user_ram::{RamAddress, UserRam};
+
+// 固定長配列のUserRam
+// SIZE: バイト数, START: スタートアドレス
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArrayRam<const SIZE: usize, const START: usize>([u8; SIZE]);
+
+impl<const SIZE: usize, const START: usize> UserRam for ArrayRam<SIZE, START> {
+    // UserRamのスタートアドレス
+    const START_ADDRESS: usize = START;
+    // UserRamの終了アドレス
+    const END_ADDRESS: usize = START + SIZE - 1;
+
+    // 初期化
+    fn new() -> Self {
+        ArrayRam([0; SIZE])
+    }
+
+    // 書き込み(範囲外はpanic)
+    fn write_to(&mut self, address: RamAddress, value: usize) -> &mut Self {
+        self.0[address.0 - START] = value as u8;
+        self
+    }
+
+    // 読み込み(範囲外はpanic)
+    fn read_from(&mut self, address: RamAddress) -> usize {
+        self.0[address.0 - START] as usize
+    }
+}
+
+#[cfg(test)]
+mod array_ram_tests {
+    use super::*;
+    use rstest::rstest;
+
+    // utility
+    // ATmega328PのSRAM相当
+    type ExampleRam = ArrayRam<0x0800, 0x0100>;
+
+    // 初期化
+    #[test]
+    fn initialize() {
+        // 初期化
+        let ram = ExampleRam::new();
+
+        // テスト
+        assert_eq!(ram, ArrayRam([0; 0x0800]));
+        assert_eq!(ExampleRam::START_ADDRESS, 0x0100);
+        assert_eq!(ExampleRam::END_ADDRESS, 0x08FF);
+    }
+
+    // 書き込み,読み込み
+    #[rstest]
+    #[case::start(0x0100, 110, 110)]
+    #[case::end(0x08FF, 42, 42)]
+    #[case::truncate(0x0300, 420, 164)]
+    fn write_read(#[case] address: usize, #[case] value: usize, #[case] expected: usize) {
+        // 初期化
+        let mut ram = ExampleRam::new();
+
+        // 書き込み,読み込み
+        let result = ram
+            .write_to(RamAddress(address), value)
+            .read_from(RamAddress(address));
+
+        // テスト
+        assert_eq!(result, expected);
+    }
+
+    // 境界外の書きテスト
+    #[rstest]
+    #[case::below_start(0x00FF)]
+    #[case::above_end(0x0900)]
+    #[should_panic]
+    fn write_out_of_boundary(#[case] address: usize) {
+        // 初期化
+        let mut ram = ExampleRam::new();
+
+        // 書き込み
+        ram.write_to(RamAddress(address), 1);
+    }
+}
@@ -0,0 +1,58 @@
+// 要素import
+use crate::registers::Flag;
+use crate::user_ram::RamAddress;
+use thiserror::Error;
+
+// マイコン全体のエラー
+#[derive(Clone, Copy, Debug, Error, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum McuError {
+    #[error(transparent)]
+    Register(#[from] RegisterError),
+    #[error(transparent)]
+    Memory(#[from] MemoryError),
+}
+
+// レジスタ操作のエラー
+#[derive(Clone, Copy, Debug, Error, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RegisterError {
+    // 存在しない汎用レジスタ
+    #[error("general register R{id} does not exist")]
+    GeneralOutOfRange { id: usize },
+    // 存在しないIOレジスタ
+    #[error("I/O register {id:#04X} does not exist")]
+    IoOutOfRange { id: usize },
+    // アーキテクチャに存在しないフラグ
+    #[error("{flag} flag is not supported by this architecture")]
+    UnsupportedFlag { flag: Flag },
+    // 0除算
+    #[error("division by zero")]
+    DivisionByZero,
+}
+
+// メモリ操作のエラー
+#[derive(Clone, Copy, Debug, Error, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MemoryError {
+    // 範囲外のアドレス
+    #[error("RAM address {address} is out of range")]
+    OutOfRange { address: RamAddress },
+}
+
+#[cfg(test)]
+mod error_tests {
+    use super::*;
+    use rstest::rstest;
+
+    // エラーメッセージ
+    #[rstest]
+    #[case::general(RegisterError::GeneralOutOfRange { id: 32 }.into(), "general register R32 does not exist")]
+    #[case::io(RegisterError::IoOutOfRange { id: 256 }.into(), "I/O register 0x100 does not exist")]
+    #[case::unsupported_flag(RegisterError::UnsupportedFlag { flag: Flag::HalfCarry }.into(), "HalfCarry flag is not supported by this architecture")]
+    #[case::division_by_zero(RegisterError::DivisionByZero.into(), "division by zero")]
+    #[case::memory(MemoryError::OutOfRange { address: RamAddress(0x0900) }.into(), "RAM address 0x0900 is out of range")]
+    fn message(#[case] error: McuError, #[case] expected: &str) {
+        assert_eq!(error.to_string(), expected);
+    }
+}
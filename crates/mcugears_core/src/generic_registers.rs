@@ -0,0 +1,393 @@
+// 要素import
+use crate::registers::{ArithmeticPolicy, Flag, RegisterType, Registers};
+use core::convert::Infallible;
+use core::marker::PhantomData;
+
+// マクロ
+// Wordの実装マクロ
+macro_rules! impl_word {
+    ($($ty:ty),+) => {
+        $(
+            impl Word for $ty {
+                const BITS: usize = <$ty>::BITS as usize;
+
+                fn truncate(value: usize) -> Self {
+                    value as $ty
+                }
+
+                fn widen(self) -> usize {
+                    self as usize
+                }
+            }
+        )+
+    };
+}
+
+// レジスタ1つ分の幅を表すトレイト
+pub trait Word: Copy + Default {
+    // ビット幅
+    const BITS: usize;
+
+    // 幅に合わせて切り捨て
+    fn truncate(value: usize) -> Self;
+    // usizeへ変換
+    fn widen(self) -> usize;
+}
+
+impl_word!(u8, u16, u32);
+
+// フラグのビット配置を表すトレイト(アーキテクチャごとに実装)
+pub trait FlagLayout {
+    // フラグのステータスレジスタ上のビット位置
+    fn flag_bit(flag: Flag) -> Option<usize>;
+}
+
+// 汎用レジスタ構造体
+// NGP: 汎用レジスタ数, NIO: IOレジスタ数
+// W: 汎用,ステータス,IOレジスタの幅, P: スタックポインタ,プログラムカウンタの幅
+// F: フラグのビット配置
+#[derive(Clone, Debug, PartialEq)]
+pub struct GenericRegisters<const NGP: usize, const NIO: usize, W, P, F> {
+    general: [W; NGP],
+    status: W,
+    stack_pointer: P,
+    program_counter: P,
+    io: [W; NIO],
+    policy: ArithmeticPolicy,
+    layout: PhantomData<F>,
+}
+
+impl<const NGP: usize, const NIO: usize, W, P, F> GenericRegisters<NGP, NIO, W, P, F> {
+    // 演算のポリシーを指定
+    pub fn with_arithmetic_policy(mut self, policy: ArithmeticPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
+
+// レジスタの実装
+impl<const NGP: usize, const NIO: usize, W: Word, P: Word, F: FlagLayout> Registers
+    for GenericRegisters<NGP, NIO, W, P, F>
+{
+    type Extended = Infallible;
+    const GENERAL_COUNT: usize = NGP;
+    const IO_COUNT: usize = NIO;
+
+    // 初期化
+    fn new() -> Self {
+        // 0初期化
+        GenericRegisters {
+            general: [W::default(); NGP],
+            status: W::default(),
+            stack_pointer: P::default(),
+            program_counter: P::default(),
+            io: [W::default(); NIO],
+            policy: ArithmeticPolicy::default(),
+            layout: PhantomData,
+        }
+    }
+
+    // レジスタ書き込み
+    fn write_to(&mut self, register_type: RegisterType, value: usize) -> &mut Self {
+        // 書き込み
+        match register_type {
+            RegisterType::General { id } => self.general[id] = W::truncate(value),
+            RegisterType::Status => self.status = W::truncate(value),
+            RegisterType::StackPointer => self.stack_pointer = P::truncate(value),
+            RegisterType::ProgramCounter => self.program_counter = P::truncate(value),
+            RegisterType::Io { id } => self.io[id] = W::truncate(value),
+            RegisterType::Extended(never) => match never {},
+        }
+
+        self
+    }
+
+    // レジスタ読み取り
+    fn read_from(&self, register_type: RegisterType) -> usize {
+        // 読み取った値を返す
+        match register_type {
+            RegisterType::General { id } => self.general[id].widen(),
+            RegisterType::Status => self.status.widen(),
+            RegisterType::StackPointer => self.stack_pointer.widen(),
+            RegisterType::ProgramCounter => self.program_counter.widen(),
+            RegisterType::Io { id } => self.io[id].widen(),
+            RegisterType::Extended(never) => match never {},
+        }
+    }
+
+    // フラグのビット位置(ステータスレジスタの幅に収まらない配置はpanic)
+    fn flag_bit(flag: Flag) -> Option<usize> {
+        let bit = F::flag_bit(flag)?;
+        assert!(
+            bit < W::BITS,
+            "{flag:?} flag bit {bit} does not fit in the {}-bit status register",
+            W::BITS
+        );
+
+        Some(bit)
+    }
+
+    // 演算のポリシー
+    fn arithmetic_policy(&self) -> ArithmeticPolicy {
+        self.policy
+    }
+}
+
+#[cfg(test)]
+mod generic_registers_tests {
+    use super::*;
+    use crate::error::RegisterError;
+    use crate::registers::{DivisionByZero, OverflowPolicy};
+    use rstest::rstest;
+
+    // utility
+    // フラグ配置(Carry,Zeroのみ)
+    #[derive(Clone, Debug, PartialEq)]
+    struct ExampleLayout;
+
+    impl FlagLayout for ExampleLayout {
+        fn flag_bit(flag: Flag) -> Option<usize> {
+            match flag {
+                Flag::Carry => Some(0),
+                Flag::Zero => Some(1),
+                _ => None,
+            }
+        }
+    }
+
+    // 8bitのステータスレジスタに収まらないフラグ配置
+    #[derive(Clone, Debug, PartialEq)]
+    struct OversizedLayout;
+
+    impl FlagLayout for OversizedLayout {
+        fn flag_bit(flag: Flag) -> Option<usize> {
+            match flag {
+                Flag::Carry => Some(8),
+                Flag::Zero => Some(usize::BITS as usize),
+                _ => None,
+            }
+        }
+    }
+
+    // 8bitレジスタ,16bitポインタ
+    type NarrowRegisters = GenericRegisters<32, 64, u8, u16, ExampleLayout>;
+    // 32bitレジスタ,32bitポインタ
+    type WideRegisters = GenericRegisters<16, 0, u32, u32, ExampleLayout>;
+
+    // registersの初期化
+    #[test]
+    fn initialize() {
+        let registers = NarrowRegisters::new();
+
+        assert_eq!(
+            registers,
+            GenericRegisters {
+                general: [0; 32],
+                status: 0,
+                stack_pointer: 0,
+                program_counter: 0,
+                io: [0; 64],
+                policy: ArithmeticPolicy::default(),
+                layout: PhantomData,
+            }
+        )
+    }
+
+    // 幅に応じた切り捨て
+    #[rstest]
+    #[case::general(RegisterType::General{id:22}, 310, 54)]
+    #[case::status(RegisterType::Status, 288, 32)]
+    #[case::stack_pointer(RegisterType::StackPointer, 65635, 99)]
+    #[case::program_counter(RegisterType::ProgramCounter, 66222, 686)]
+    #[case::io(RegisterType::Io{id:63}, 400, 144)]
+    fn narrow_truncation(
+        #[case] register_type: RegisterType,
+        #[case] value: usize,
+        #[case] expected: usize,
+    ) {
+        // 初期化
+        let mut registers = NarrowRegisters::new();
+
+        //書き込み,読み込み
+        let result = registers
+            .write_to(register_type, value)
+            .read_from(register_type);
+
+        // テスト
+        assert_eq!(result, expected);
+    }
+
+    // 32bit幅では切り捨てない
+    #[rstest]
+    #[case::general(RegisterType::General{id:15}, 0x1234_5678)]
+    #[case::program_counter(RegisterType::ProgramCounter, 0x0800_0100)]
+    fn wide_write_read(#[case] register_type: RegisterType, #[case] value: usize) {
+        // 初期化
+        let mut registers = WideRegisters::new();
+
+        //書き込み,読み込み
+        let result = registers
+            .write_to(register_type, value)
+            .read_from(register_type);
+
+        // テスト
+        assert_eq!(result, value);
+    }
+
+    // 境界外の書きテスト
+    #[rstest]
+    #[case::general_max(RegisterType::General{id:32}, 117)]
+    #[case::io_max(RegisterType::Io{id:64}, 98)]
+    #[should_panic]
+    fn write_out_of_boundary(#[case] register_type: RegisterType, #[case] value: usize) {
+        // 初期化
+        let mut registers = NarrowRegisters::new();
+
+        //書き込み
+        registers.write_to(register_type, value);
+    }
+
+    // フラグ配置の反映
+    #[test]
+    fn flag() {
+        // 初期化
+        let mut registers = NarrowRegisters::new();
+
+        // 書き込み
+        registers.set_flag(Flag::Zero, true);
+
+        // テスト
+        assert_eq!(registers.read_from(RegisterType::Status), 0b10);
+    }
+
+    // 存在しないフラグはtry_*でエラー
+    #[rstest]
+    #[case::supported(Flag::Zero, Ok(true))]
+    #[case::unsupported(Flag::HalfCarry, Err(RegisterError::UnsupportedFlag { flag: Flag::HalfCarry }))]
+    fn try_flag(#[case] target: Flag, #[case] expected: Result<bool, RegisterError>) {
+        // 初期化
+        let mut registers = NarrowRegisters::new();
+
+        // 書き込み,読み込み
+        let result = registers
+            .try_set_flag(target, true)
+            .and_then(|registers| registers.try_flag(target));
+
+        // テスト
+        assert_eq!(result, expected);
+        // 失敗時はステータスレジスタを変更しない
+        assert_eq!(
+            registers.read_from(RegisterType::Status),
+            expected.map_or(0, |_| 0b10)
+        );
+    }
+
+    // ステータスレジスタに収まらないビット位置はフラグ名とビット位置でpanic
+    #[test]
+    #[should_panic(expected = "Carry flag bit 8 does not fit in the 8-bit status register")]
+    fn flag_bit_beyond_status_width() {
+        // 初期化
+        let mut registers = GenericRegisters::<4, 0, u8, u16, OversizedLayout>::new();
+
+        // 書き込み
+        registers.set_flag(Flag::Carry, true);
+    }
+
+    // 読み込みも同様にpanic
+    #[test]
+    #[should_panic(expected = "Carry flag bit 8 does not fit in the 8-bit status register")]
+    fn flag_bit_beyond_status_width_read() {
+        // 初期化
+        let registers = GenericRegisters::<4, 0, u8, u16, OversizedLayout>::new();
+
+        // 読み込み
+        registers.flag(Flag::Carry);
+    }
+
+    // usizeにも収まらないビット位置
+    #[test]
+    #[should_panic(expected = "Zero flag bit 64")]
+    fn flag_bit_beyond_usize() {
+        // 初期化
+        let mut registers = GenericRegisters::<4, 0, u8, u16, OversizedLayout>::new();
+
+        // 書き込み
+        registers.set_flag(Flag::Zero, true);
+    }
+
+    // 演算のポリシー
+    #[rstest]
+    #[case::add_wrap(OverflowPolicy::Wrap, 250, 94)]
+    #[case::add_saturate(OverflowPolicy::Saturate, 250, 255)]
+    #[case::add_in_range(OverflowPolicy::Saturate, 63, 163)]
+    fn overflow_policy(
+        #[case] overflow: OverflowPolicy,
+        #[case] value: usize,
+        #[case] expected: usize,
+    ) {
+        // 初期化
+        let policy = ArithmeticPolicy {
+            overflow,
+            ..Default::default()
+        };
+        let mut registers = NarrowRegisters::new().with_arithmetic_policy(policy);
+        let register_type = RegisterType::General { id: 4 };
+        registers.write_to(register_type, 100);
+
+        // 演算
+        let result = registers
+            .add_to(register_type, value)
+            .read_from(register_type);
+
+        // テスト
+        assert_eq!(result, expected);
+    }
+
+    // 減算の飽和
+    #[test]
+    fn sub_saturate() {
+        // 初期化
+        let policy = ArithmeticPolicy {
+            overflow: OverflowPolicy::Saturate,
+            ..Default::default()
+        };
+        let mut registers = NarrowRegisters::new().with_arithmetic_policy(policy);
+        let register_type = RegisterType::General { id: 7 };
+        registers.write_to(register_type, 100);
+
+        // 演算
+        let result = registers
+            .sub_from(register_type, 108)
+            .read_from(register_type);
+
+        // テスト
+        assert_eq!(result, 0);
+    }
+
+    // 0除算
+    #[rstest]
+    #[case::saturate(DivisionByZero::Saturate, Ok(0xFF))]
+    #[case::defined(DivisionByZero::Defined(0), Ok(0))]
+    #[case::trap(DivisionByZero::Trap, Err(RegisterError::DivisionByZero))]
+    fn division_by_zero_policy(
+        #[case] division_by_zero: DivisionByZero,
+        #[case] expected: Result<usize, RegisterError>,
+    ) {
+        // 初期化
+        let policy = ArithmeticPolicy {
+            division_by_zero,
+            ..Default::default()
+        };
+        let mut registers = NarrowRegisters::new().with_arithmetic_policy(policy);
+        let register_type = RegisterType::General { id: 8 };
+        registers.write_to(register_type, 100);
+
+        // 演算
+        let result = registers
+            .try_div_from(register_type, 0)
+            .map(|registers| registers.read_from(register_type));
+
+        // テスト
+        assert_eq!(result, expected);
+    }
+}
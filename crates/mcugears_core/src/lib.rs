@@ -1,5 +1,10 @@
 #![allow(dead_code)]
+// std featureが無効な場合はno_stdでビルド(テスト時はstdを使用)
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 // 要素import
+pub mod array_ram;
+pub mod error;
+pub mod generic_registers;
 pub mod registers;
 pub mod user_ram;
 
@@ -1,41 +1,402 @@
+// 要素import
+use crate::error::RegisterError;
+use core::cmp::Reverse;
+use core::convert::Infallible;
+use core::fmt;
+
 // マクロ
 // 演算書き込み実装のマクロ
 macro_rules! impl_operation {
-    ($fn_name:ident, $op:ident) => {
-        fn $fn_name(&mut self, register_type: RegisterType, value: usize) -> &mut Self {
-            // 演算
-            self.write_to(register_type, self.read_from(register_type).$op(value))
+    ($fn_name:ident, $try_name:ident, $operation:expr) => {
+        fn $fn_name(
+            &mut self,
+            register_type: RegisterType<Self::Extended>,
+            value: usize,
+        ) -> &mut Self {
+            // 演算(エラーはpanic)
+            operate(self, register_type, $operation, value)
+                .unwrap_or_else(|error| panic!("{error}"))
+        }
+
+        fn $try_name(
+            &mut self,
+            register_type: RegisterType<Self::Extended>,
+            value: usize,
+        ) -> Result<&mut Self, RegisterError> {
+            // 演算(存在しないレジスタ,0除算はエラー)
+            operate(self, register_type, $operation, value)
         }
     };
 }
 
 // レジスタを表す構造体
-trait Registers {
+pub trait Registers {
+    // アーキテクチャ固有のレジスタ(無い場合はInfallible)
+    type Extended: Copy;
+    // 汎用レジスタ数
+    const GENERAL_COUNT: usize;
+    // IOレジスタ数
+    const IO_COUNT: usize;
+
     // 初期化
     fn new() -> Self;
     // 書き込み
-    fn write_to(&mut self, register_type: RegisterType, value: usize) -> &mut Self;
+    fn write_to(&mut self, register_type: RegisterType<Self::Extended>, value: usize) -> &mut Self;
     // 読み込み
-    fn read_from(&self, register_type: RegisterType) -> usize;
+    fn read_from(&self, register_type: RegisterType<Self::Extended>) -> usize;
+    // フラグのステータスレジスタ上のビット位置(アーキテクチャに存在しない場合はNone)
+    fn flag_bit(flag: Flag) -> Option<usize>;
+    // 0除算,オーバーフロー時の挙動
+    fn arithmetic_policy(&self) -> ArithmeticPolicy {
+        ArithmeticPolicy::default()
+    }
+
+    // 書き込み(存在しないレジスタはpanicせずにエラー)
+    fn try_write_to(
+        &mut self,
+        register_type: RegisterType<Self::Extended>,
+        value: usize,
+    ) -> Result<&mut Self, RegisterError> {
+        check_register::<Self>(register_type)?;
+        Ok(self.write_to(register_type, value))
+    }
+    // 読み込み(存在しないレジスタはpanicせずにエラー)
+    fn try_read_from(
+        &self,
+        register_type: RegisterType<Self::Extended>,
+    ) -> Result<usize, RegisterError> {
+        check_register::<Self>(register_type)?;
+        Ok(self.read_from(register_type))
+    }
+
+    // フラグ読み込み
+    fn flag(&self, flag: Flag) -> bool {
+        self.read_from(RegisterType::Status) & flag_mask::<Self>(flag) != 0
+    }
+    // フラグ書き込み
+    fn set_flag(&mut self, flag: Flag, value: bool) -> &mut Self {
+        let mask = flag_mask::<Self>(flag);
+        let status = self.read_from(RegisterType::Status);
+
+        // 該当ビットのみ書き換え
+        let registers = match value {
+            true => self.write_to(RegisterType::Status, status | mask),
+            false => self.write_to(RegisterType::Status, status & !mask),
+        };
+
+        // ステータスレジスタの幅で切り捨てられていないか確認
+        if value && registers.read_from(RegisterType::Status) & mask == 0 {
+            panic!(
+                "{flag:?} flag bit {} does not fit in the status register",
+                mask.trailing_zeros()
+            );
+        }
+
+        registers
+    }
+    // フラグ読み込み(存在しないフラグはpanicせずにエラー)
+    fn try_flag(&self, flag: Flag) -> Result<bool, RegisterError> {
+        check_flag::<Self>(flag)?;
+        Ok(self.flag(flag))
+    }
+    // フラグ書き込み(存在しないフラグはpanicせずにエラー)
+    fn try_set_flag(&mut self, flag: Flag, value: bool) -> Result<&mut Self, RegisterError> {
+        check_flag::<Self>(flag)?;
+        Ok(self.set_flag(flag, value))
+    }
+    // フラグの表示用
+    fn status_flags(&self) -> StatusFlags<'_, Self>
+    where
+        Self: Sized,
+    {
+        StatusFlags(self)
+    }
+    // レジスタ内容の表示用
+    fn dump(&self) -> RegisterDump<'_, Self>
+    where
+        Self: Sized,
+    {
+        RegisterDump(self)
+    }
 
     // 加算
-    impl_operation!(add_to, wrapping_add);
+    impl_operation!(add_to, try_add_to, Operation::Add);
     // 減算
-    impl_operation!(sub_from, wrapping_sub);
+    impl_operation!(sub_from, try_sub_from, Operation::Sub);
     // 乗算
-    impl_operation!(mul_to, wrapping_mul);
+    impl_operation!(mul_to, try_mul_to, Operation::Mul);
     // 徐算
-    impl_operation!(div_from, wrapping_div);
+    impl_operation!(div_from, try_div_from, Operation::Div);
 }
 
-// レジスタ種類を表す列挙型
+// 演算の種類
 #[derive(Clone, Copy)]
-pub enum RegisterType {
+enum Operation {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+// ポリシーに従った演算と書き込み
+fn operate<R: Registers + ?Sized>(
+    registers: &mut R,
+    register_type: RegisterType<R::Extended>,
+    operation: Operation,
+    value: usize,
+) -> Result<&mut R, RegisterError> {
+    let policy = registers.arithmetic_policy();
+    let current = registers.try_read_from(register_type)?;
+
+    // 演算
+    let result = match (operation, policy.overflow) {
+        (Operation::Add, OverflowPolicy::Wrap) => current.wrapping_add(value),
+        (Operation::Add, OverflowPolicy::Saturate) => current.saturating_add(value),
+        (Operation::Sub, OverflowPolicy::Wrap) => current.wrapping_sub(value),
+        (Operation::Sub, OverflowPolicy::Saturate) => current.saturating_sub(value),
+        (Operation::Mul, OverflowPolicy::Wrap) => current.wrapping_mul(value),
+        (Operation::Mul, OverflowPolicy::Saturate) => current.saturating_mul(value),
+        (Operation::Div, _) => match (value, policy.division_by_zero) {
+            (0, DivisionByZero::Trap) => return Err(RegisterError::DivisionByZero),
+            (0, DivisionByZero::Saturate) => usize::MAX,
+            (0, DivisionByZero::Defined(result)) => result,
+            _ => current / value,
+        },
+    };
+
+    // 書き込み(レジスタ幅を超えた場合は最大値に飽和)
+    registers.try_write_to(register_type, result)?;
+    if policy.overflow == OverflowPolicy::Saturate && registers.read_from(register_type) != result {
+        registers.write_to(register_type, usize::MAX);
+    }
+
+    Ok(registers)
+}
+
+// レジスタの存在確認
+fn check_register<R: Registers + ?Sized>(
+    register_type: RegisterType<R::Extended>,
+) -> Result<(), RegisterError> {
+    match register_type {
+        RegisterType::General { id } if id >= R::GENERAL_COUNT => {
+            Err(RegisterError::GeneralOutOfRange { id })
+        }
+        RegisterType::Io { id } if id >= R::IO_COUNT => Err(RegisterError::IoOutOfRange { id }),
+        _ => Ok(()),
+    }
+}
+
+// フラグの存在確認
+fn check_flag<R: Registers + ?Sized>(flag: Flag) -> Result<(), RegisterError> {
+    match R::flag_bit(flag) {
+        Some(_) => Ok(()),
+        None => Err(RegisterError::UnsupportedFlag { flag }),
+    }
+}
+
+// フラグのビットマスク(存在しないフラグ,usizeに収まらないビット位置はpanic)
+fn flag_mask<R: Registers + ?Sized>(flag: Flag) -> usize {
+    match R::flag_bit(flag) {
+        Some(bit) => u32::try_from(bit)
+            .ok()
+            .and_then(|shift| 1usize.checked_shl(shift))
+            .unwrap_or_else(|| panic!("{flag:?} flag bit {bit} does not fit in a usize")),
+        None => panic!("{}", RegisterError::UnsupportedFlag { flag }),
+    }
+}
+
+// レジスタ種類を表す列挙型
+// Eはアーキテクチャ固有のレジスタ(RAMPZ, DPTRなど)
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RegisterType<E = Infallible> {
     General { id: usize },
     Status,
     StackPointer,
     ProgramCounter,
     Io { id: usize },
+    Extended(E),
+}
+
+// 演算のポリシー
+// デフォルトは0除算でエラー(panic),オーバーフローは折り返し
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ArithmeticPolicy {
+    pub division_by_zero: DivisionByZero,
+    pub overflow: OverflowPolicy,
+}
+
+// 0除算時の挙動
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DivisionByZero {
+    // エラー
+    #[default]
+    Trap,
+    // レジスタの最大値
+    Saturate,
+    // アーキテクチャで定義された値
+    Defined(usize),
+}
+
+// オーバーフロー時の挙動
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum OverflowPolicy {
+    // 折り返し(切り捨て)
+    #[default]
+    Wrap,
+    // レジスタの最大値(減算は0)に飽和
+    Saturate,
+}
+
+// ステータスレジスタのフラグを表す列挙型
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Flag {
+    Carry,
+    Zero,
+    Negative,
+    Overflow,
+    Sign,
+    HalfCarry,
+    // ビットコピー用(AVRのT)
+    BitCopy,
+    // 割り込み許可
+    InterruptEnable,
+}
+
+impl Flag {
+    // 全フラグ
+    pub const ALL: [Flag; 8] = [
+        Flag::Carry,
+        Flag::Zero,
+        Flag::Negative,
+        Flag::Overflow,
+        Flag::Sign,
+        Flag::HalfCarry,
+        Flag::BitCopy,
+        Flag::InterruptEnable,
+    ];
+
+    // 1文字表記
+    pub fn symbol(self) -> char {
+        match self {
+            Flag::Carry => 'C',
+            Flag::Zero => 'Z',
+            Flag::Negative => 'N',
+            Flag::Overflow => 'V',
+            Flag::Sign => 'S',
+            Flag::HalfCarry => 'H',
+            Flag::BitCopy => 'T',
+            Flag::InterruptEnable => 'I',
+        }
+    }
+}
+
+// 表示
+// R5, SREG, SP, PC, IO[0x3F]
+impl<E: fmt::Display> fmt::Display for RegisterType<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegisterType::General { id } => write!(f, "R{id}"),
+            RegisterType::Status => write!(f, "SREG"),
+            RegisterType::StackPointer => write!(f, "SP"),
+            RegisterType::ProgramCounter => write!(f, "PC"),
+            RegisterType::Io { id } => write!(f, "IO[{id:#04X}]"),
+            RegisterType::Extended(register) => register.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for Flag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+// ステータスレジスタのフラグ表示
+// {}  : 上位ビットから1文字ずつ、立っていないフラグは'-' (例: I-----ZC)
+// {:#}: フラグ名で列挙 (例: InterruptEnable=1 ... Carry=1)
+pub struct StatusFlags<'a, R: Registers>(&'a R);
+
+// アーキテクチャに存在するフラグを上位ビット順に並べる
+fn ordered_flags<R: Registers>() -> impl Iterator<Item = Flag> {
+    let mut flags = Flag::ALL;
+    flags.sort_unstable_by_key(|flag| Reverse(R::flag_bit(*flag)));
+
+    flags
+        .into_iter()
+        .filter(|flag| R::flag_bit(*flag).is_some())
+}
+
+impl<R: Registers> fmt::Display for StatusFlags<'_, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, flag) in ordered_flags::<R>().enumerate() {
+            let value = self.0.flag(flag);
+            match f.alternate() {
+                true if index > 0 => write!(f, " {flag}={}", value as u8)?,
+                true => write!(f, "{flag}={}", value as u8)?,
+                false if value => write!(f, "{}", flag.symbol())?,
+                false => write!(f, "-")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// defmtでの表示(Displayの1行表示と同じ)
+#[cfg(feature = "defmt")]
+impl<R: Registers> defmt::Format for StatusFlags<'_, R> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        for flag in ordered_flags::<R>() {
+            match self.0.flag(flag) {
+                true => defmt::write!(f, "{=char}", flag.symbol()),
+                false => defmt::write!(f, "-"),
+            }
+        }
+    }
+}
+
+// レジスタ内容の表示
+// {}  : 汎用レジスタを8個ずつ並べ、最後の行にSREG,SP,PC
+//   R0=0x00 R1=0x00 ... R7=0x00
+//   ...
+//   SREG=I-----ZC SP=0x08FF PC=0x0000
+// {:#}: 全てを1行に並べる (例: R0=0x00 ... R31=0x00 SREG=I-----ZC SP=0x08FF PC=0x0000)
+// IOレジスタ,Extendedは含まない
+pub struct RegisterDump<'a, R: Registers>(&'a R);
+
+impl<R: Registers> fmt::Display for RegisterDump<'_, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // 1行表示では改行の代わりに空白で区切る
+        let line_break = match f.alternate() {
+            true => " ",
+            false => "\n",
+        };
+
+        for id in 0..R::GENERAL_COUNT {
+            let value = self.0.read_from(RegisterType::General { id });
+            match id {
+                0 => write!(f, "R{id}={value:#04X}")?,
+                _ if id % 8 == 0 => write!(f, "{line_break}R{id}={value:#04X}")?,
+                _ => write!(f, " R{id}={value:#04X}")?,
+            }
+        }
+        if R::GENERAL_COUNT > 0 {
+            write!(f, "{line_break}")?;
+        }
+
+        write!(
+            f,
+            "SREG={} SP={:#06X} PC={:#06X}",
+            self.0.status_flags(),
+            self.0.read_from(RegisterType::StackPointer),
+            self.0.read_from(RegisterType::ProgramCounter)
+        )
+    }
 }
 
 #[cfg(test)]
@@ -51,10 +412,27 @@ mod register_tests {
         stack_pointer: u16,
         program_counter: u16,
         io: [u8; 256],
+        rampz: u8,
+    }
+
+    // アーキテクチャ固有のレジスタ
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum ExampleExtended {
+        Rampz,
+    }
+
+    impl fmt::Display for ExampleExtended {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "RAMPZ")
+        }
     }
 
     // レジスタの実装
     impl Registers for ExampleRegisters {
+        type Extended = ExampleExtended;
+        const GENERAL_COUNT: usize = 32;
+        const IO_COUNT: usize = 256;
+
         // 初期化
         fn new() -> Self {
             // 0初期化
@@ -64,11 +442,16 @@ mod register_tests {
                 stack_pointer: 0,
                 program_counter: 0,
                 io: [0; 256],
+                rampz: 0,
             }
         }
 
         // レジスタ書き込み
-        fn write_to(&mut self, register_type: RegisterType, value: usize) -> &mut Self {
+        fn write_to(
+            &mut self,
+            register_type: RegisterType<Self::Extended>,
+            value: usize,
+        ) -> &mut Self {
             // 書き込み
             match register_type {
                 RegisterType::General { id } => self.general[id] = value as u8,
@@ -76,13 +459,14 @@ mod register_tests {
                 RegisterType::StackPointer => self.stack_pointer = value as u16,
                 RegisterType::ProgramCounter => self.program_counter = value as u16,
                 RegisterType::Io { id } => self.io[id] = value as u8,
+                RegisterType::Extended(ExampleExtended::Rampz) => self.rampz = value as u8,
             }
 
             self
         }
 
         // レジスタ読み取り
-        fn read_from(&self, register_type: RegisterType) -> usize {
+        fn read_from(&self, register_type: RegisterType<Self::Extended>) -> usize {
             // 読み取った値を返す
             match register_type {
                 RegisterType::General { id } => self.general[id].into(),
@@ -90,6 +474,21 @@ mod register_tests {
                 RegisterType::StackPointer => self.stack_pointer.into(),
                 RegisterType::ProgramCounter => self.program_counter.into(),
                 RegisterType::Io { id } => self.io[id].into(),
+                RegisterType::Extended(ExampleExtended::Rampz) => self.rampz.into(),
+            }
+        }
+
+        // フラグのビット位置(AVRと同じ配置)
+        fn flag_bit(flag: Flag) -> Option<usize> {
+            match flag {
+                Flag::Carry => Some(0),
+                Flag::Zero => Some(1),
+                Flag::Negative => Some(2),
+                Flag::Overflow => Some(3),
+                Flag::Sign => Some(4),
+                Flag::HalfCarry => Some(5),
+                Flag::BitCopy => Some(6),
+                Flag::InterruptEnable => Some(7),
             }
         }
     }
@@ -111,6 +510,7 @@ mod register_tests {
                     stack_pointer: 0,
                     program_counter: 0,
                     io: [0; 256],
+                    rampz: 0,
                 }
             )
         }
@@ -139,6 +539,7 @@ mod register_tests {
                 stack_pointer: 0,
                 program_counter: 0,
                 io: [0; 256],
+                rampz: 0,
             };
             expected.general[14] = 140;
 
@@ -168,7 +569,11 @@ mod register_tests {
         #[case::stack_pointer(RegisterType::StackPointer, 528)]
         #[case::program_counter(RegisterType::ProgramCounter, 1204)]
         #[case::io(RegisterType::Io{id:105}, 21)]
-        fn write_read_variously(#[case] register_type: RegisterType, #[case] value: usize) {
+        #[case::extended(RegisterType::Extended(ExampleExtended::Rampz), 3)]
+        fn write_read_variously(
+            #[case] register_type: RegisterType<ExampleExtended>,
+            #[case] value: usize,
+        ) {
             // 初期化
             let mut registers = ExampleRegisters::new();
 
@@ -187,7 +592,10 @@ mod register_tests {
         #[case::general_max(RegisterType::General{id:31}, 42)]
         #[case::io_min(RegisterType::Io{id:0}, 110)]
         #[case::io_max(RegisterType::Io{id:255}, 223)]
-        fn read_write_on_boundary(#[case] register_type: RegisterType, #[case] value: usize) {
+        fn read_write_on_boundary(
+            #[case] register_type: RegisterType<ExampleExtended>,
+            #[case] value: usize,
+        ) {
             // 初期化
             let mut registers = ExampleRegisters::new();
 
@@ -205,7 +613,10 @@ mod register_tests {
         #[case::general_max(RegisterType::General{id:32}, 117)]
         #[case::io_max(RegisterType::Io{id:256}, 98)]
         #[should_panic]
-        fn write_out_of_boundary(#[case] register_type: RegisterType, #[case] value: usize) {
+        fn write_out_of_boundary(
+            #[case] register_type: RegisterType<ExampleExtended>,
+            #[case] value: usize,
+        ) {
             // 初期化
             let mut registers = ExampleRegisters::new();
 
@@ -218,7 +629,7 @@ mod register_tests {
         #[case::general_max(RegisterType::General{id:32})]
         #[case::io_max(RegisterType::Io{id:256})]
         #[should_panic]
-        fn read_out_of_boundary(#[case] register_type: RegisterType) {
+        fn read_out_of_boundary(#[case] register_type: RegisterType<ExampleExtended>) {
             // 初期化
             let registers = ExampleRegisters::new();
 
@@ -233,8 +644,9 @@ mod register_tests {
         #[case::stack_pointer(RegisterType::StackPointer, 65635, 99)]
         #[case::program_counter(RegisterType::ProgramCounter, 66222, 686)]
         #[case::io(RegisterType::Io{id:28}, 400, 144)]
+        #[case::extended(RegisterType::Extended(ExampleExtended::Rampz), 260, 4)]
         fn write_read_truncation(
-            #[case] register_type: RegisterType,
+            #[case] register_type: RegisterType<ExampleExtended>,
             #[case] value: usize,
             #[case] expected: usize,
         ) {
@@ -265,7 +677,7 @@ mod register_tests {
                     #[case::$pattern($reg_type,$val,$expected)]
                 )+
                 fn $test_name(
-                    #[case] register_type: RegisterType,
+                    #[case] register_type: RegisterType<ExampleExtended>,
                     #[case] value: usize,
                     #[case] expected: usize,
                 ) {
@@ -308,4 +720,281 @@ mod register_tests {
             #[case::truncate(RegisterType::General{id:20}, 1000, 0)]
         );
     }
+
+    // フラグ操作のテスト
+    #[cfg(test)]
+    mod flag {
+        use super::*;
+        use rstest::rstest;
+
+        // Carryを8bitのステータスレジスタの外に配置した誤った実装
+        struct OversizedFlagRegisters(ExampleRegisters);
+
+        impl Registers for OversizedFlagRegisters {
+            type Extended = ExampleExtended;
+            const GENERAL_COUNT: usize = ExampleRegisters::GENERAL_COUNT;
+            const IO_COUNT: usize = ExampleRegisters::IO_COUNT;
+
+            fn new() -> Self {
+                OversizedFlagRegisters(ExampleRegisters::new())
+            }
+
+            fn write_to(
+                &mut self,
+                register_type: RegisterType<Self::Extended>,
+                value: usize,
+            ) -> &mut Self {
+                self.0.write_to(register_type, value);
+                self
+            }
+
+            fn read_from(&self, register_type: RegisterType<Self::Extended>) -> usize {
+                self.0.read_from(register_type)
+            }
+
+            fn flag_bit(flag: Flag) -> Option<usize> {
+                match flag {
+                    Flag::Carry => Some(8),
+                    _ => None,
+                }
+            }
+        }
+
+        // 書き込み,読み込み
+        #[rstest]
+        #[case::carry(Flag::Carry, 0b0000_0001)]
+        #[case::zero(Flag::Zero, 0b0000_0010)]
+        #[case::half_carry(Flag::HalfCarry, 0b0010_0000)]
+        #[case::interrupt_enable(Flag::InterruptEnable, 0b1000_0000)]
+        fn set_flag(#[case] flag: Flag, #[case] expected: usize) {
+            // 初期化
+            let mut registers = ExampleRegisters::new();
+
+            // 書き込み
+            registers.set_flag(flag, true);
+
+            // テスト
+            assert!(registers.flag(flag));
+            assert_eq!(registers.read_from(RegisterType::Status), expected);
+        }
+
+        // 他のビットに影響しない
+        #[rstest]
+        #[case::set(Flag::Overflow, true, 0b1010_1110)]
+        #[case::clear(Flag::Negative, false, 0b1010_0010)]
+        fn keep_other_bits(#[case] flag: Flag, #[case] value: bool, #[case] expected: usize) {
+            // 初期化
+            let mut registers = ExampleRegisters::new();
+            registers.write_to(RegisterType::Status, 0b1010_0110);
+
+            // 書き込み
+            let result = registers
+                .set_flag(flag, value)
+                .read_from(RegisterType::Status);
+
+            // テスト
+            assert_eq!(result, expected);
+        }
+
+        // ステータスレジスタの幅に収まらないビット位置はpanic
+        #[test]
+        #[should_panic(expected = "Carry flag bit 8 does not fit in the status register")]
+        fn set_flag_beyond_status_width() {
+            // 初期化
+            let mut registers = OversizedFlagRegisters::new();
+
+            // 書き込み
+            registers.set_flag(Flag::Carry, true);
+        }
+    }
+
+    // 表示のテスト
+    #[cfg(test)]
+    mod display {
+        use super::*;
+        use rstest::rstest;
+
+        // レジスタ名
+        #[rstest]
+        #[case::general(RegisterType::General{id:5}, "R5")]
+        #[case::status(RegisterType::Status, "SREG")]
+        #[case::stack_pointer(RegisterType::StackPointer, "SP")]
+        #[case::program_counter(RegisterType::ProgramCounter, "PC")]
+        #[case::io(RegisterType::Io{id:63}, "IO[0x3F]")]
+        #[case::extended(RegisterType::Extended(ExampleExtended::Rampz), "RAMPZ")]
+        fn register_name(
+            #[case] register_type: RegisterType<ExampleExtended>,
+            #[case] expected: &str,
+        ) {
+            assert_eq!(register_type.to_string(), expected);
+        }
+
+        // フラグの1行表示
+        #[test]
+        fn status_flags() {
+            // 初期化
+            let mut registers = ExampleRegisters::new();
+            registers
+                .set_flag(Flag::InterruptEnable, true)
+                .set_flag(Flag::Zero, true)
+                .set_flag(Flag::Carry, true);
+
+            // テスト
+            assert_eq!(registers.status_flags().to_string(), "I-----ZC");
+        }
+
+        // フラグ名での表示
+        #[test]
+        fn status_flags_alternate() {
+            // 初期化
+            let mut registers = ExampleRegisters::new();
+            registers.set_flag(Flag::Sign, true);
+
+            // テスト
+            assert_eq!(
+                format!("{:#}", registers.status_flags()),
+                "InterruptEnable=0 BitCopy=0 HalfCarry=0 Sign=1 Overflow=0 Negative=0 Zero=0 Carry=0"
+            );
+        }
+
+        // レジスタ内容の表示
+        #[test]
+        fn dump() {
+            // 初期化
+            let mut registers = ExampleRegisters::new();
+            registers
+                .write_to(RegisterType::General { id: 1 }, 0x5A)
+                .write_to(RegisterType::General { id: 31 }, 0xFF)
+                .write_to(RegisterType::StackPointer, 0x08FF)
+                .write_to(RegisterType::ProgramCounter, 0x0100)
+                .set_flag(Flag::Zero, true);
+
+            // テスト
+            assert_eq!(
+                registers.dump().to_string(),
+                "R0=0x00 R1=0x5A R2=0x00 R3=0x00 R4=0x00 R5=0x00 R6=0x00 R7=0x00\n\
+                 R8=0x00 R9=0x00 R10=0x00 R11=0x00 R12=0x00 R13=0x00 R14=0x00 R15=0x00\n\
+                 R16=0x00 R17=0x00 R18=0x00 R19=0x00 R20=0x00 R21=0x00 R22=0x00 R23=0x00\n\
+                 R24=0x00 R25=0x00 R26=0x00 R27=0x00 R28=0x00 R29=0x00 R30=0x00 R31=0xFF\n\
+                 SREG=------Z- SP=0x08FF PC=0x0100"
+            );
+        }
+
+        // レジスタ内容の1行表示
+        #[test]
+        fn dump_alternate() {
+            // 初期化
+            let mut registers = ExampleRegisters::new();
+            registers
+                .write_to(RegisterType::General { id: 8 }, 0x12)
+                .write_to(RegisterType::StackPointer, 0x08FF)
+                .set_flag(Flag::InterruptEnable, true);
+
+            // テスト
+            assert_eq!(
+                format!("{:#}", registers.dump()),
+                "R0=0x00 R1=0x00 R2=0x00 R3=0x00 R4=0x00 R5=0x00 R6=0x00 R7=0x00 \
+                 R8=0x12 R9=0x00 R10=0x00 R11=0x00 R12=0x00 R13=0x00 R14=0x00 R15=0x00 \
+                 R16=0x00 R17=0x00 R18=0x00 R19=0x00 R20=0x00 R21=0x00 R22=0x00 R23=0x00 \
+                 R24=0x00 R25=0x00 R26=0x00 R27=0x00 R28=0x00 R29=0x00 R30=0x00 R31=0x00 \
+                 SREG=I------- SP=0x08FF PC=0x0000"
+            );
+        }
+    }
+
+    // エラーを返す操作のテスト
+    #[cfg(test)]
+    mod fallible {
+        use super::*;
+        use rstest::rstest;
+
+        // 存在するレジスタは通常通り読み書き
+        #[test]
+        fn write_read() {
+            // 初期化
+            let mut registers = ExampleRegisters::new();
+            let register_type = RegisterType::Io { id: 255 };
+
+            //書き込み,読み込み
+            let result = registers
+                .try_write_to(register_type, 310)
+                .and_then(|registers| registers.try_read_from(register_type));
+
+            // テスト
+            assert_eq!(result, Ok(54));
+        }
+
+        // 境界外の書き込み
+        #[rstest]
+        #[case::general_max(RegisterType::General{id:32}, RegisterError::GeneralOutOfRange { id: 32 })]
+        #[case::io_max(RegisterType::Io{id:256}, RegisterError::IoOutOfRange { id: 256 })]
+        fn write_out_of_boundary(
+            #[case] register_type: RegisterType<ExampleExtended>,
+            #[case] expected: RegisterError,
+        ) {
+            // 初期化
+            let mut registers = ExampleRegisters::new();
+
+            // 書き込み
+            let result = registers.try_write_to(register_type, 1).map(|_| ());
+
+            // テスト
+            assert_eq!(result, Err(expected));
+        }
+
+        // 境界外の読み込み
+        #[test]
+        fn read_out_of_boundary() {
+            // 初期化
+            let registers = ExampleRegisters::new();
+
+            // 読み込み
+            let result = registers.try_read_from(RegisterType::General { id: 40 });
+
+            // テスト
+            assert_eq!(result, Err(RegisterError::GeneralOutOfRange { id: 40 }));
+        }
+
+        // 境界外での演算
+        #[test]
+        fn calculation_out_of_boundary() {
+            // 初期化
+            let mut registers = ExampleRegisters::new();
+
+            // 演算
+            let result = registers
+                .try_add_to(RegisterType::General { id: 32 }, 1)
+                .map(|_| ());
+
+            // テスト
+            assert_eq!(result, Err(RegisterError::GeneralOutOfRange { id: 32 }));
+        }
+
+        // 0除算
+        #[test]
+        fn division_by_zero() {
+            // 初期化
+            let mut registers = ExampleRegisters::new();
+            let register_type = RegisterType::General { id: 3 };
+            registers.write_to(register_type, 100);
+
+            // 演算
+            let result = registers.try_div_from(register_type, 0).map(|_| ());
+
+            // テスト
+            assert_eq!(result, Err(RegisterError::DivisionByZero));
+            assert_eq!(registers.read_from(register_type), 100);
+        }
+
+        // 0除算(エラーを返さない場合はpanic)
+        #[test]
+        #[should_panic]
+        fn division_by_zero_panic() {
+            // 初期化
+            let mut registers = ExampleRegisters::new();
+
+            // 演算
+            registers.div_from(RegisterType::General { id: 3 }, 0);
+        }
+    }
 }
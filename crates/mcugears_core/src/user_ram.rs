@@ -1,3 +1,7 @@
+// 要素import
+use crate::error::MemoryError;
+use core::fmt;
+
 // userのアクセスできるram
 pub trait UserRam {
     // UserRamのスタートアドレス
@@ -12,10 +16,41 @@ pub trait UserRam {
     fn write_to(&mut self, address: RamAddress, value: usize) -> &mut Self;
     //読み込み
     fn read_from(&mut self, address: RamAddress) -> usize;
+
+    // 書き込み(範囲外はpanicせずにエラー)
+    fn try_write_to(
+        &mut self,
+        address: RamAddress,
+        value: usize,
+    ) -> Result<&mut Self, MemoryError> {
+        check_address::<Self>(address)?;
+        Ok(self.write_to(address, value))
+    }
+    // 読み込み(範囲外はpanicせずにエラー)
+    fn try_read_from(&mut self, address: RamAddress) -> Result<usize, MemoryError> {
+        check_address::<Self>(address)?;
+        Ok(self.read_from(address))
+    }
+}
+
+// アドレスの範囲確認
+fn check_address<R: UserRam + ?Sized>(address: RamAddress) -> Result<(), MemoryError> {
+    match (R::START_ADDRESS..=R::END_ADDRESS).contains(&address.0) {
+        true => Ok(()),
+        false => Err(MemoryError::OutOfRange { address }),
+    }
 }
 // Ramのアドレス
 #[derive(Clone, Copy, Debug, PartialEq)]
-pub struct RamAddress(usize);
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RamAddress(pub usize);
+
+// 表示(例: 0x01FF)
+impl fmt::Display for RamAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#06X}", self.0)
+    }
+}
 
 //  テスト
 #[cfg(test)]
@@ -96,4 +131,59 @@ mod user_ram_tests {
             assert_eq!(user_ram.read_from(RamAddress(address)), expected);
         }
     }
+
+    // エラーを返す操作
+    #[cfg(test)]
+    mod fallible {
+        use super::*;
+        use rstest::rstest;
+
+        // 範囲内の読み書き
+        #[test]
+        fn write_read() {
+            // 初期化
+            let mut user_ram = ExampleUserRam::new();
+
+            // 書き込み,読み込み
+            let result = user_ram
+                .try_write_to(RamAddress(0x08FF), 42)
+                .and_then(|user_ram| user_ram.try_read_from(RamAddress(0x08FF)));
+
+            // テスト
+            assert_eq!(result, Ok(42));
+        }
+
+        // 範囲外の読み書き
+        #[rstest]
+        #[case::below_start(0x00FF)]
+        #[case::above_end(0x0900)]
+        fn out_of_range(#[case] address: usize) {
+            // 初期化
+            let mut user_ram = ExampleUserRam::new();
+            let expected = Err(MemoryError::OutOfRange {
+                address: RamAddress(address),
+            });
+
+            // テスト
+            assert_eq!(
+                user_ram.try_write_to(RamAddress(address), 1).map(|_| ()),
+                expected
+            );
+            assert_eq!(
+                user_ram.try_read_from(RamAddress(address)),
+                expected.map(|_| 0)
+            );
+        }
+    }
+
+    // 表示
+    #[cfg(test)]
+    mod display {
+        use super::*;
+
+        #[test]
+        fn address() {
+            assert_eq!(RamAddress(0x1FF).to_string(), "0x01FF");
+        }
+    }
 }
@@ -0,0 +1,391 @@
+// 要素import
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{
+    Data, DeriveInput, Expr, Fields, Ident, LitInt, Type, parse_macro_input, spanned::Spanned,
+};
+
+// Registersトレイトのderiveマクロ
+// 各フィールドに#[register(...)]でレジスタ種類を指定する
+//   general, io                        : 配列フィールド
+//   status, stack_pointer, program_counter : 単一フィールド
+// 指定のないフィールドはDefault::default()で初期化される
+// アーキテクチャ固有のレジスタ(RegisterType::Extended)は持たない
+// フラグのビット位置は構造体に#[flags(Carry = 0, Zero = 1, ...)]で指定する
+//   指定のないフラグはアーキテクチャに存在しないものとして扱う
+//   ステータスレジスタの幅に収まらないビット位置はコンパイルエラー
+// ArithmeticPolicy型のフィールドに#[arithmetic_policy]を付けると演算のポリシーとして使う
+//   初期値はArithmeticPolicy::default()で、フィールドを書き換えて変更する
+// ジェネリクスを持つ構造体には使えない
+#[proc_macro_derive(Registers, attributes(register, flags, arithmetic_policy))]
+pub fn derive_registers(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_registers(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// レジスタ種類
+#[derive(Clone, Copy, PartialEq)]
+enum RegisterKind {
+    General,
+    Status,
+    StackPointer,
+    ProgramCounter,
+    Io,
+}
+
+impl RegisterKind {
+    // 全種類
+    const ALL: [RegisterKind; 5] = [
+        RegisterKind::General,
+        RegisterKind::Status,
+        RegisterKind::StackPointer,
+        RegisterKind::ProgramCounter,
+        RegisterKind::Io,
+    ];
+
+    // 属性名から変換
+    fn from_ident(ident: &Ident) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| ident == kind.attribute_name())
+    }
+
+    // 属性名
+    fn attribute_name(self) -> &'static str {
+        match self {
+            RegisterKind::General => "general",
+            RegisterKind::Status => "status",
+            RegisterKind::StackPointer => "stack_pointer",
+            RegisterKind::ProgramCounter => "program_counter",
+            RegisterKind::Io => "io",
+        }
+    }
+
+    // 配列フィールドかどうか
+    fn is_array(self) -> bool {
+        matches!(self, RegisterKind::General | RegisterKind::Io)
+    }
+}
+
+// レジスタとして扱うフィールド
+struct RegisterField<'a> {
+    kind: RegisterKind,
+    name: &'a Ident,
+    // 値の型(配列の場合は要素の型)
+    value_type: &'a Type,
+    // 配列の長さ
+    length: Option<&'a Expr>,
+}
+
+impl RegisterField<'_> {
+    // 初期値
+    fn initializer(&self) -> TokenStream2 {
+        match self.length {
+            Some(length) => quote!([0; #length]),
+            None => quote!(0),
+        }
+    }
+
+    // 書き込み処理
+    fn write(&self) -> TokenStream2 {
+        let (name, value_type) = (self.name, self.value_type);
+        match self.kind.is_array() {
+            true => quote!(self.#name[id] = value as #value_type),
+            false => quote!(self.#name = value as #value_type),
+        }
+    }
+
+    // 読み込み処理
+    fn read(&self) -> TokenStream2 {
+        let name = self.name;
+        match self.kind.is_array() {
+            true => quote!(self.#name[id] as usize),
+            false => quote!(self.#name as usize),
+        }
+    }
+
+    // 対応するRegisterTypeのパターン
+    fn pattern(&self) -> TokenStream2 {
+        let register_type = quote!(::mcugears_core::registers::RegisterType);
+        match self.kind {
+            RegisterKind::General => quote!(#register_type::General { id }),
+            RegisterKind::Status => quote!(#register_type::Status),
+            RegisterKind::StackPointer => quote!(#register_type::StackPointer),
+            RegisterKind::ProgramCounter => quote!(#register_type::ProgramCounter),
+            RegisterKind::Io => quote!(#register_type::Io { id }),
+        }
+    }
+}
+
+// implの生成
+fn expand_registers(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    // 名前付きフィールドの構造体のみ対応
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "Registers can only be derived for structs with named fields",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "Registers can only be derived for structs",
+            ));
+        }
+    };
+
+    // ジェネリクスは非対応(フィールドの型をキャストと幅の確認に使うため)
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "Registers cannot be derived for generic structs",
+        ));
+    }
+
+    // フィールドの振り分け
+    let mut registers: Vec<RegisterField> = Vec::new();
+    let mut others: Vec<&Ident> = Vec::new();
+    let mut policy: Option<(&Ident, &Type)> = None;
+    for field in fields {
+        let name = field.ident.as_ref().expect("named field");
+
+        // 演算のポリシー
+        if let Some(attr) = field
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("arithmetic_policy"))
+        {
+            attr.meta.require_path_only()?;
+            if policy.is_some() {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "duplicate #[arithmetic_policy] field",
+                ));
+            }
+            if parse_register_kind(field)?.is_some() {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "#[arithmetic_policy] field cannot also be a #[register(...)] field",
+                ));
+            }
+            policy = Some((name, &field.ty));
+        }
+
+        match parse_register_kind(field)? {
+            Some(kind) => {
+                // 重複チェック
+                if registers.iter().any(|register| register.kind == kind) {
+                    return Err(syn::Error::new(
+                        field.span(),
+                        format!("duplicate #[register({})] field", kind.attribute_name()),
+                    ));
+                }
+                registers.push(register_field(kind, name, &field.ty)?);
+            }
+            None => others.push(name),
+        }
+    }
+
+    // 全種類揃っているか確認
+    let mut arms = Vec::new();
+    for kind in RegisterKind::ALL {
+        match registers.iter().find(|register| register.kind == kind) {
+            Some(register) => arms.push(register),
+            None => {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    format!("missing #[register({})] field", kind.attribute_name()),
+                ));
+            }
+        }
+    }
+
+    // フラグの配置
+    let flags = parse_flags(input)?;
+    let flag_names = flags.iter().map(|(flag, _)| flag);
+    let flag_bits = flags.iter().map(|(_, bit)| bit);
+    // ビット位置がステータスレジスタの幅に収まるかをコンパイル時に確認
+    let status_type = arms
+        .iter()
+        .find(|register| register.kind == RegisterKind::Status)
+        .map(|register| register.value_type);
+    let flag_checks = flags.iter().map(|(flag, bit)| {
+        let message = format!("flag `{flag}` bit {bit} does not fit in the status register");
+        quote_spanned! {bit.span()=>
+            const _: () = ::core::assert!(#bit < <#status_type>::BITS as usize, #message);
+        }
+    });
+
+    // 各処理の生成
+    let name = &input.ident;
+    let register_names = registers.iter().map(|register| register.name);
+    let initializers = registers.iter().map(RegisterField::initializer);
+    let patterns: Vec<_> = arms.iter().map(|register| register.pattern()).collect();
+    let writes = arms.iter().map(|register| register.write());
+    let reads = arms.iter().map(|register| register.read());
+    let length_of = |kind| {
+        arms.iter()
+            .find(|register| register.kind == kind)
+            .and_then(|register| register.length)
+    };
+    let policy = policy.map(|(policy, ty)| {
+        // 型が違う場合はフィールドの型を指してエラーにする
+        let body = quote_spanned!(ty.span()=> self.#policy);
+        quote! {
+            // 演算のポリシー
+            fn arithmetic_policy(&self) -> ::mcugears_core::registers::ArithmeticPolicy {
+                #body
+            }
+        }
+    });
+    let general_count = length_of(RegisterKind::General);
+    let io_count = length_of(RegisterKind::Io);
+
+    Ok(quote! {
+        #(#flag_checks)*
+
+        impl ::mcugears_core::registers::Registers for #name {
+            type Extended = ::core::convert::Infallible;
+            const GENERAL_COUNT: usize = #general_count;
+            const IO_COUNT: usize = #io_count;
+
+            // 初期化
+            fn new() -> Self {
+                #name {
+                    #(#register_names: #initializers,)*
+                    #(#others: ::core::default::Default::default(),)*
+                }
+            }
+
+            // レジスタ書き込み
+            fn write_to(
+                &mut self,
+                register_type: ::mcugears_core::registers::RegisterType,
+                value: usize,
+            ) -> &mut Self {
+                match register_type {
+                    #(#patterns => #writes,)*
+                    ::mcugears_core::registers::RegisterType::Extended(never) => match never {},
+                }
+
+                self
+            }
+
+            // レジスタ読み取り
+            fn read_from(&self, register_type: ::mcugears_core::registers::RegisterType) -> usize {
+                match register_type {
+                    #(#patterns => #reads,)*
+                    ::mcugears_core::registers::RegisterType::Extended(never) => match never {},
+                }
+            }
+
+            // フラグのビット位置
+            #[allow(unreachable_patterns)]
+            fn flag_bit(flag: ::mcugears_core::registers::Flag) -> ::core::option::Option<usize> {
+                match flag {
+                    #(::mcugears_core::registers::Flag::#flag_names => ::core::option::Option::Some(#flag_bits),)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            #policy
+        }
+    })
+}
+
+// #[flags(...)]属性の読み取り
+fn parse_flags(input: &DeriveInput) -> syn::Result<Vec<(Ident, LitInt)>> {
+    let mut flags: Vec<(Ident, LitInt)> = Vec::new();
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("flags"))
+    {
+        attr.parse_nested_meta(|meta| {
+            let flag = meta.path.require_ident()?.clone();
+            let bit: LitInt = meta.value()?.parse()?;
+
+            // 重複チェック
+            if flags.iter().any(|(other, _)| *other == flag) {
+                return Err(meta.error(format!("duplicate flag `{flag}`")));
+            }
+            bit.base10_parse::<usize>()?;
+
+            flags.push((flag, bit));
+            Ok(())
+        })?;
+    }
+
+    Ok(flags)
+}
+
+// #[register(...)]属性の読み取り
+fn parse_register_kind(field: &syn::Field) -> syn::Result<Option<RegisterKind>> {
+    let mut kind = None;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("register"))
+    {
+        if kind.is_some() {
+            return Err(syn::Error::new(
+                attr.span(),
+                "only one #[register(...)] attribute is allowed per field",
+            ));
+        }
+
+        let ident: Ident = attr.parse_args()?;
+        kind = Some(RegisterKind::from_ident(&ident).ok_or_else(|| {
+            syn::Error::new(
+                ident.span(),
+                "expected one of: general, status, stack_pointer, program_counter, io",
+            )
+        })?);
+    }
+
+    Ok(kind)
+}
+
+// フィールドの型の確認
+fn register_field<'a>(
+    kind: RegisterKind,
+    name: &'a Ident,
+    ty: &'a Type,
+) -> syn::Result<RegisterField<'a>> {
+    match (kind.is_array(), ty) {
+        (true, Type::Array(array)) => Ok(RegisterField {
+            kind,
+            name,
+            value_type: &array.elem,
+            length: Some(&array.len),
+        }),
+        (true, _) => Err(syn::Error::new(
+            ty.span(),
+            format!(
+                "#[register({})] field must be an array",
+                kind.attribute_name()
+            ),
+        )),
+        (false, Type::Array(_)) => Err(syn::Error::new(
+            ty.span(),
+            format!(
+                "#[register({})] field must not be an array",
+                kind.attribute_name()
+            ),
+        )),
+        (false, _) => Ok(RegisterField {
+            kind,
+            name,
+            value_type: ty,
+            length: None,
+        }),
+    }
+}
@@ -0,0 +1,225 @@
+// #[derive(Registers)]のテスト
+use mcugears_core::error::RegisterError;
+use mcugears_core::registers::{
+    ArithmeticPolicy, DivisionByZero, Flag, OverflowPolicy, RegisterType, Registers,
+};
+use mcugears_derive::Registers;
+use rstest::rstest;
+
+// utility
+// deriveしたレジスタ構造体
+#[derive(Clone, Debug, PartialEq, Registers)]
+#[flags(Carry = 0, Zero = 1, Negative = 2, InterruptEnable = 7)]
+struct DerivedRegisters {
+    #[register(general)]
+    general: [u8; 32],
+    #[register(status)]
+    status: u8,
+    #[register(stack_pointer)]
+    stack_pointer: u16,
+    #[register(program_counter)]
+    program_counter: u16,
+    #[register(io)]
+    io: [u8; 256],
+    // レジスタ以外のフィールド
+    label: Option<&'static str>,
+}
+
+// 演算のポリシーを持つレジスタ構造体
+#[derive(Registers)]
+struct PolicyRegisters {
+    #[register(general)]
+    general: [u8; 4],
+    #[register(status)]
+    status: u8,
+    #[register(stack_pointer)]
+    stack_pointer: u16,
+    #[register(program_counter)]
+    program_counter: u16,
+    #[register(io)]
+    io: [u8; 4],
+    #[arithmetic_policy]
+    policy: ArithmeticPolicy,
+}
+
+// registersの初期化
+#[test]
+fn initialize() {
+    let registers = DerivedRegisters::new();
+
+    assert_eq!(
+        registers,
+        DerivedRegisters {
+            general: [0; 32],
+            status: 0,
+            stack_pointer: 0,
+            program_counter: 0,
+            io: [0; 256],
+            label: None,
+        }
+    )
+}
+
+// 様々なレジスタの種類の読み書きに対応
+#[rstest]
+#[case::general(RegisterType::General{id:2}, 200)]
+#[case::status(RegisterType::Status, 121)]
+#[case::stack_pointer(RegisterType::StackPointer, 528)]
+#[case::program_counter(RegisterType::ProgramCounter, 1204)]
+#[case::io(RegisterType::Io{id:105}, 21)]
+fn write_read_variously(#[case] register_type: RegisterType, #[case] value: usize) {
+    // 初期化
+    let mut registers = DerivedRegisters::new();
+
+    //書き込み,読み込み
+    let result = registers
+        .write_to(register_type, value)
+        .read_from(register_type);
+
+    // テスト
+    assert_eq!(result, value);
+}
+
+// 切り捨て処理(フィールドの型の幅に従う)
+#[rstest]
+#[case::general(RegisterType::General{id:22}, 310, 54)]
+#[case::status(RegisterType::Status, 288, 32)]
+#[case::stack_pointer(RegisterType::StackPointer, 65635, 99)]
+#[case::program_counter(RegisterType::ProgramCounter, 66222, 686)]
+#[case::io(RegisterType::Io{id:28}, 400, 144)]
+fn write_read_truncation(
+    #[case] register_type: RegisterType,
+    #[case] value: usize,
+    #[case] expected: usize,
+) {
+    // 初期化
+    let mut registers = DerivedRegisters::new();
+
+    //書き込み,読み込み
+    let result = registers
+        .write_to(register_type, value)
+        .read_from(register_type);
+
+    // テスト
+    assert_eq!(result, expected);
+}
+
+// 境界外の書きテスト
+#[rstest]
+#[case::general_max(RegisterType::General{id:32}, 117)]
+#[case::io_max(RegisterType::Io{id:256}, 98)]
+#[should_panic]
+fn write_out_of_boundary(#[case] register_type: RegisterType, #[case] value: usize) {
+    // 初期化
+    let mut registers = DerivedRegisters::new();
+
+    //書き込み
+    registers.write_to(register_type, value);
+}
+
+// トレイトの演算関数も使える
+#[test]
+fn calculation() {
+    // 初期化
+    let mut registers = DerivedRegisters::new();
+    let register_type = RegisterType::General { id: 30 };
+    registers.write_to(register_type, 100);
+
+    // 操作
+    let result = registers
+        .add_to(register_type, 250)
+        .read_from(register_type);
+
+    // テスト
+    assert_eq!(result, 94);
+}
+
+// #[arithmetic_policy]のフィールドに従って演算
+#[rstest]
+#[case::default(ArithmeticPolicy::default(), Ok(94))]
+#[case::saturate(ArithmeticPolicy { overflow: OverflowPolicy::Saturate, ..Default::default() }, Ok(255))]
+fn arithmetic_policy(
+    #[case] policy: ArithmeticPolicy,
+    #[case] expected: Result<usize, RegisterError>,
+) {
+    // 初期化
+    let mut registers = PolicyRegisters::new();
+    registers.policy = policy;
+    let register_type = RegisterType::General { id: 3 };
+    registers.write_to(register_type, 100);
+
+    // 操作
+    let result = registers
+        .try_add_to(register_type, 250)
+        .map(|registers| registers.read_from(register_type));
+
+    // テスト
+    assert_eq!(registers.arithmetic_policy(), policy);
+    assert_eq!(result, expected);
+}
+
+// 0除算のポリシー
+#[test]
+fn division_by_zero_policy() {
+    // 初期化
+    let mut registers = PolicyRegisters::new();
+    let register_type = RegisterType::General { id: 0 };
+
+    // 初期値はエラー
+    assert_eq!(
+        registers.try_div_from(register_type, 0).map(|_| ()),
+        Err(RegisterError::DivisionByZero)
+    );
+
+    // 定義された値に変更
+    registers.policy.division_by_zero = DivisionByZero::Defined(0xFF);
+    let result = registers
+        .div_from(register_type, 0)
+        .read_from(register_type);
+
+    // テスト
+    assert_eq!(result, 0xFF);
+}
+
+// 指定したビット位置でフラグを読み書き
+#[rstest]
+#[case::carry(Flag::Carry, 0b0000_0001)]
+#[case::negative(Flag::Negative, 0b0000_0100)]
+#[case::interrupt_enable(Flag::InterruptEnable, 0b1000_0000)]
+fn write_read_flag(#[case] flag: Flag, #[case] expected: usize) {
+    // 初期化
+    let mut registers = DerivedRegisters::new();
+
+    // 書き込み
+    registers.set_flag(flag, true);
+
+    // テスト
+    assert!(registers.flag(flag));
+    assert_eq!(registers.read_from(RegisterType::Status), expected);
+}
+
+// 指定のないフラグは存在しない
+#[test]
+#[should_panic]
+fn unsupported_flag() {
+    // 初期化
+    let mut registers = DerivedRegisters::new();
+
+    // 書き込み
+    registers.set_flag(Flag::HalfCarry, true);
+}
+
+// 配列の長さからレジスタ数が決まる
+#[rstest]
+#[case::general(RegisterType::General{id:32}, RegisterError::GeneralOutOfRange { id: 32 })]
+#[case::io(RegisterType::Io{id:256}, RegisterError::IoOutOfRange { id: 256 })]
+fn try_write_out_of_boundary(#[case] register_type: RegisterType, #[case] expected: RegisterError) {
+    // 初期化
+    let mut registers = DerivedRegisters::new();
+
+    // 書き込み
+    let result = registers.try_write_to(register_type, 1).map(|_| ());
+
+    // テスト
+    assert_eq!(result, Err(expected));
+}
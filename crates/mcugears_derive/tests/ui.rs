@@ -0,0 +1,7 @@
+// #[derive(Registers)]のコンパイルエラーのテスト
+// 期待するエラーはtests/ui/*.stderr(TRYBUILD=overwriteで更新)
+#[test]
+fn compile_errors() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,22 @@
+use mcugears_core::registers::ArithmeticPolicy;
+use mcugears_derive::Registers;
+
+#[derive(Registers)]
+struct DuplicateArithmeticPolicy {
+    #[register(general)]
+    general: [u8; 4],
+    #[register(status)]
+    status: u8,
+    #[register(stack_pointer)]
+    stack_pointer: u16,
+    #[register(program_counter)]
+    program_counter: u16,
+    #[register(io)]
+    io: [u8; 4],
+    #[arithmetic_policy]
+    policy: ArithmeticPolicy,
+    #[arithmetic_policy]
+    shadow_policy: ArithmeticPolicy,
+}
+
+fn main() {}
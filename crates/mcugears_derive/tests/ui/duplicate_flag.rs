@@ -0,0 +1,18 @@
+use mcugears_derive::Registers;
+
+#[derive(Registers)]
+#[flags(Carry = 0, Carry = 1)]
+struct DuplicateFlag {
+    #[register(general)]
+    general: [u8; 4],
+    #[register(status)]
+    status: u8,
+    #[register(stack_pointer)]
+    stack_pointer: u16,
+    #[register(program_counter)]
+    program_counter: u16,
+    #[register(io)]
+    io: [u8; 4],
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use mcugears_derive::Registers;
+
+#[derive(Registers)]
+enum EnumInput {
+    General,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use mcugears_derive::Registers;
+
+#[derive(Registers)]
+struct GenericStruct<T> {
+    #[register(general)]
+    general: [T; 4],
+    #[register(status)]
+    status: T,
+    #[register(stack_pointer)]
+    stack_pointer: u16,
+    #[register(program_counter)]
+    program_counter: u16,
+    #[register(io)]
+    io: [u8; 4],
+}
+
+fn main() {}
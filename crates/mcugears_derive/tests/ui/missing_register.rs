@@ -0,0 +1,15 @@
+use mcugears_derive::Registers;
+
+#[derive(Registers)]
+struct MissingRegister {
+    #[register(general)]
+    general: [u8; 4],
+    #[register(status)]
+    status: u8,
+    #[register(stack_pointer)]
+    stack_pointer: u16,
+    #[register(io)]
+    io: [u8; 4],
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use mcugears_derive::Registers;
+
+#[derive(Registers)]
+struct MultipleRegisterAttributes {
+    #[register(general)]
+    general: [u8; 4],
+    #[register(status)]
+    #[register(stack_pointer)]
+    status: u8,
+    #[register(program_counter)]
+    program_counter: u16,
+    #[register(io)]
+    io: [u8; 4],
+}
+
+fn main() {}
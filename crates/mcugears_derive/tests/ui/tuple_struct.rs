@@ -0,0 +1,6 @@
+use mcugears_derive::Registers;
+
+#[derive(Registers)]
+struct TupleStruct([u8; 4], u8);
+
+fn main() {}
@@ -0,0 +1,19 @@
+use mcugears_derive::Registers;
+
+#[derive(Registers)]
+struct UnknownRegisterKind {
+    #[register(general)]
+    general: [u8; 4],
+    #[register(status)]
+    status: u8,
+    #[register(stack_pointer)]
+    stack_pointer: u16,
+    #[register(program_counter)]
+    program_counter: u16,
+    #[register(io)]
+    io: [u8; 4],
+    #[register(accumulator)]
+    accumulator: u8,
+}
+
+fn main() {}
@@ -0,0 +1,94 @@
+// トレイト実装の適合性テストキット
+// 外部のアーキテクチャクレートはマクロ1つでテスト一式を生成できる
+//
+//     mcugears_testkit::registers_conformance!(avr_registers, AvrRegisters);
+//     mcugears_testkit::user_ram_conformance!(avr_sram, AvrSram);
+//
+// 個別の確認関数はregisters,user_ramモジュールから直接呼ぶこともできる
+// proptest用のstrategyと不変条件はpropertiesモジュールにある
+// 要素import
+pub mod properties;
+pub mod registers;
+pub mod user_ram;
+
+// Registers実装のテスト一式を生成
+#[macro_export]
+macro_rules! registers_conformance {
+    ($module:ident, $registers:ty) => {
+        #[cfg(test)]
+        mod $module {
+            use super::*;
+
+            #[test]
+            fn round_trip() {
+                $crate::registers::round_trip::<$registers>();
+            }
+
+            #[test]
+            fn isolation() {
+                $crate::registers::isolation::<$registers>();
+            }
+
+            #[test]
+            fn boundary() {
+                $crate::registers::boundary::<$registers>();
+            }
+
+            #[test]
+            fn out_of_range_panics() {
+                $crate::registers::out_of_range_panics::<$registers>();
+            }
+
+            #[test]
+            fn flag_truth_table() {
+                $crate::registers::flag_truth_table::<$registers>();
+            }
+
+            #[test]
+            fn pointer_update_laws() {
+                $crate::registers::pointer_update_laws::<$registers>();
+            }
+
+            #[test]
+            fn arithmetic_inverse() {
+                $crate::registers::arithmetic_inverse::<$registers>();
+            }
+        }
+    };
+}
+
+// UserRam実装のテスト一式を生成
+#[macro_export]
+macro_rules! user_ram_conformance {
+    ($module:ident, $user_ram:ty) => {
+        #[cfg(test)]
+        mod $module {
+            use super::*;
+
+            #[test]
+            fn address_range() {
+                $crate::user_ram::address_range::<$user_ram>();
+            }
+
+            #[test]
+            fn round_trip() {
+                $crate::user_ram::round_trip::<$user_ram>();
+            }
+
+            #[test]
+            fn isolation() {
+                $crate::user_ram::isolation::<$user_ram>();
+            }
+
+            #[test]
+            fn boundary() {
+                $crate::user_ram::boundary::<$user_ram>();
+            }
+
+            #[test]
+            fn out_of_range_panics() {
+                $crate::user_ram::out_of_range_panics::<$user_ram>();
+            }
+        }
+    };
+}
@@ -0,0 +1,134 @@
+// proptestによる性質テスト用のstrategyと不変条件
+//
+//     proptest! {
+//         #[test]
+//         fn add_sub(
+//             registers in register_state::<AvrRegisters>(),
+//             register_type in general_register::<AvrRegisters>(),
+//             operand in any::<usize>(),
+//         ) {
+//             add_sub_restores(registers, register_type, operand)?;
+//         }
+//     }
+// 要素import
+use core::fmt::Debug;
+use mcugears_core::registers::{Flag, OverflowPolicy, RegisterType, Registers};
+use proptest::prelude::*;
+
+// strategy
+// 汎用レジスタ
+pub fn general_register<R: Registers>() -> impl Strategy<Value = RegisterType<R::Extended>>
+where
+    R::Extended: Debug,
+{
+    (0..R::GENERAL_COUNT).prop_map(|id| RegisterType::General { id })
+}
+
+// Extendedを除く全レジスタ
+pub fn register_type<R: Registers>() -> impl Strategy<Value = RegisterType<R::Extended>>
+where
+    R::Extended: Debug,
+{
+    // 通し番号をレジスタに割り当てる(SREG,SP,PC,汎用,IOの順)
+    (0..3 + R::GENERAL_COUNT + R::IO_COUNT).prop_map(|index| match index {
+        0 => RegisterType::Status,
+        1 => RegisterType::StackPointer,
+        2 => RegisterType::ProgramCounter,
+        id if id < 3 + R::GENERAL_COUNT => RegisterType::General { id: id - 3 },
+        id => RegisterType::Io {
+            id: id - 3 - R::GENERAL_COUNT,
+        },
+    })
+}
+
+// アーキテクチャに存在するフラグ(1つも無い場合はpanic)
+pub fn flag<R: Registers>() -> impl Strategy<Value = Flag> {
+    let supported: Vec<Flag> = Flag::ALL
+        .into_iter()
+        .filter(|flag| R::flag_bit(*flag).is_some())
+        .collect();
+    assert!(
+        !supported.is_empty(),
+        "{} maps no flags, so the flag strategy has nothing to generate",
+        core::any::type_name::<R>()
+    );
+
+    proptest::sample::select(supported)
+}
+
+// ランダムな値で埋めたレジスタ
+pub fn register_state<R: Registers + Debug>() -> impl Strategy<Value = R> {
+    let general = proptest::collection::vec(any::<usize>(), R::GENERAL_COUNT);
+    let io = proptest::collection::vec(any::<usize>(), R::IO_COUNT);
+    let pointers = any::<(usize, usize, usize)>();
+
+    (general, io, pointers).prop_map(|(general, io, (status, stack_pointer, program_counter))| {
+        let mut registers = R::new();
+        for (id, value) in general.into_iter().enumerate() {
+            registers.write_to(RegisterType::General { id }, value);
+        }
+        for (id, value) in io.into_iter().enumerate() {
+            registers.write_to(RegisterType::Io { id }, value);
+        }
+        registers
+            .write_to(RegisterType::Status, status)
+            .write_to(RegisterType::StackPointer, stack_pointer)
+            .write_to(RegisterType::ProgramCounter, program_counter);
+
+        registers
+    })
+}
+
+// 不変条件
+// 同じ値の加算と減算で元に戻る(折り返しポリシーのみ)
+pub fn add_sub_restores<R: Registers>(
+    mut registers: R,
+    register_type: RegisterType<R::Extended>,
+    operand: usize,
+) -> Result<(), TestCaseError> {
+    prop_assume!(registers.arithmetic_policy().overflow == OverflowPolicy::Wrap);
+    let before = registers.read_from(register_type);
+
+    let after = registers
+        .add_to(register_type, operand)
+        .sub_from(register_type, operand)
+        .read_from(register_type);
+    prop_assert_eq!(after, before);
+
+    Ok(())
+}
+
+// 書き込んだ値はレジスタ幅で切り捨てられて読める
+pub fn write_read_truncates<R: Registers>(
+    mut registers: R,
+    register_type: RegisterType<R::Extended>,
+    value: usize,
+) -> Result<(), TestCaseError> {
+    let mask = registers
+        .write_to(register_type, usize::MAX)
+        .read_from(register_type);
+
+    let result = registers
+        .write_to(register_type, value)
+        .read_from(register_type);
+    prop_assert_eq!(result, value & mask);
+
+    Ok(())
+}
+
+// フラグ操作がビット演算の参照モデルと一致する
+pub fn flags_match_reference<R: Registers>(
+    mut registers: R,
+    flag: Flag,
+    value: bool,
+) -> Result<(), TestCaseError> {
+    let bit = R::flag_bit(flag).expect("flag strategy yields supported flags only");
+    let status = registers.read_from(RegisterType::Status);
+    let expected = (status & !(1 << bit)) | ((value as usize) << bit);
+
+    registers.set_flag(flag, value);
+    prop_assert_eq!(registers.read_from(RegisterType::Status), expected);
+    prop_assert_eq!(registers.flag(flag), value);
+
+    Ok(())
+}
@@ -0,0 +1,224 @@
+// 要素import
+use mcugears_core::error::RegisterError;
+use mcugears_core::registers::{Flag, OverflowPolicy, RegisterType, Registers};
+use std::panic;
+
+// 全レジスタ(Extendedを除く)
+fn all_registers<R: Registers>() -> impl Iterator<Item = RegisterType<R::Extended>> {
+    let general = (0..R::GENERAL_COUNT).map(|id| RegisterType::General { id });
+    let io = (0..R::IO_COUNT).map(|id| RegisterType::Io { id });
+
+    [
+        RegisterType::Status,
+        RegisterType::StackPointer,
+        RegisterType::ProgramCounter,
+    ]
+    .into_iter()
+    .chain(general)
+    .chain(io)
+}
+
+// 失敗メッセージ用のレジスタ名
+fn label<E>(register_type: RegisterType<E>) -> String {
+    match register_type {
+        RegisterType::General { id } => format!("R{id}"),
+        RegisterType::Status => "SREG".to_string(),
+        RegisterType::StackPointer => "SP".to_string(),
+        RegisterType::ProgramCounter => "PC".to_string(),
+        RegisterType::Io { id } => format!("IO[{id:#04X}]"),
+        RegisterType::Extended(_) => "extended register".to_string(),
+    }
+}
+
+// レジスタ幅のマスク(全ビットを立てて読み戻した値)
+fn width_mask<R: Registers>(register_type: RegisterType<R::Extended>) -> usize {
+    R::new()
+        .write_to(register_type, usize::MAX)
+        .read_from(register_type)
+}
+
+// 読み書きの往復で値が保たれ、幅を超えた分は切り捨てられる
+pub fn round_trip<R: Registers>() {
+    for register_type in all_registers::<R>() {
+        let mask = width_mask::<R>(register_type);
+        assert_ne!(mask, 0, "{} cannot hold any bit", label(register_type));
+
+        for value in [0, 1, 0x5A, mask, mask.wrapping_add(1).wrapping_add(0x5A)] {
+            let result = R::new()
+                .write_to(register_type, value)
+                .read_from(register_type);
+            assert_eq!(
+                result,
+                value & mask,
+                "{} read back {result:#X} after writing {value:#X}",
+                label(register_type)
+            );
+        }
+    }
+}
+
+// 書き込みは他のレジスタに影響しない
+pub fn isolation<R: Registers>() {
+    let initial = R::new();
+
+    for (index, written) in all_registers::<R>().enumerate() {
+        let mut registers = R::new();
+        registers.write_to(written, usize::MAX);
+
+        let others = all_registers::<R>()
+            .enumerate()
+            .filter(|(other_index, _)| *other_index != index);
+        for (_, other) in others {
+            assert_eq!(
+                registers.read_from(other),
+                initial.read_from(other),
+                "writing {} changed {}",
+                label(written),
+                label(other)
+            );
+        }
+    }
+}
+
+// 範囲外のレジスタはtry_*でエラーになる
+pub fn boundary<R: Registers>() {
+    let mut registers = R::new();
+    let general = RegisterType::General {
+        id: R::GENERAL_COUNT,
+    };
+    let io = RegisterType::Io { id: R::IO_COUNT };
+
+    assert_eq!(
+        registers.try_read_from(general),
+        Err(RegisterError::GeneralOutOfRange {
+            id: R::GENERAL_COUNT
+        })
+    );
+    assert_eq!(
+        registers.try_write_to(general, 1).map(|_| ()),
+        Err(RegisterError::GeneralOutOfRange {
+            id: R::GENERAL_COUNT
+        })
+    );
+    assert_eq!(
+        registers.try_read_from(io),
+        Err(RegisterError::IoOutOfRange { id: R::IO_COUNT })
+    );
+    assert_eq!(
+        registers.try_write_to(io, 1).map(|_| ()),
+        Err(RegisterError::IoOutOfRange { id: R::IO_COUNT })
+    );
+}
+
+// 範囲外のレジスタへの書き込みはpanicする
+pub fn out_of_range_panics<R: Registers>() {
+    let outside = [
+        RegisterType::General {
+            id: R::GENERAL_COUNT,
+        },
+        RegisterType::Io { id: R::IO_COUNT },
+    ];
+
+    for register_type in outside {
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            R::new().write_to(register_type, 1);
+        }));
+        assert!(
+            result.is_err(),
+            "writing {} did not panic",
+            label(register_type)
+        );
+    }
+}
+
+// 各フラグは自分のビットだけを読み書きする
+pub fn flag_truth_table<R: Registers>() {
+    let supported: Vec<Flag> = Flag::ALL
+        .into_iter()
+        .filter(|flag| R::flag_bit(*flag).is_some())
+        .collect();
+
+    for &flag in &supported {
+        // 0から立てる
+        let mut registers = R::new();
+        registers.write_to(RegisterType::Status, 0);
+        registers.set_flag(flag, true);
+        for &other in &supported {
+            assert_eq!(
+                registers.flag(other),
+                other == flag,
+                "setting {flag} left {other} = {}",
+                registers.flag(other)
+            );
+        }
+
+        // 全ビットから下ろす
+        registers.write_to(RegisterType::Status, usize::MAX);
+        registers.set_flag(flag, false);
+        for &other in &supported {
+            assert_eq!(
+                registers.flag(other),
+                other != flag,
+                "clearing {flag} left {other} = {}",
+                registers.flag(other)
+            );
+        }
+    }
+}
+
+// PC,SPの加減算は可逆で1ずつ進む
+pub fn pointer_update_laws<R: Registers>() {
+    for register_type in [RegisterType::ProgramCounter, RegisterType::StackPointer] {
+        let mut registers = R::new();
+        registers.write_to(register_type, 0x10);
+
+        let incremented = registers.add_to(register_type, 1).read_from(register_type);
+        assert_eq!(incremented, 0x11, "{} + 1", label(register_type));
+
+        let restored = registers
+            .add_to(register_type, 3)
+            .sub_from(register_type, 4)
+            .read_from(register_type);
+        assert_eq!(restored, 0x10, "{} + 3 - 4", label(register_type));
+    }
+}
+
+// 汎用レジスタの加算と減算は打ち消し合う
+// 折り返しポリシーでは最大値+1が0に、飽和ポリシーでは最大値+1が最大値、0-1が0になる
+pub fn arithmetic_inverse<R: Registers>() {
+    for id in 0..R::GENERAL_COUNT {
+        let register_type = RegisterType::General { id };
+        let mut registers = R::new();
+        registers.write_to(register_type, 0x20);
+
+        let restored = registers
+            .add_to(register_type, 0x10)
+            .sub_from(register_type, 0x10)
+            .read_from(register_type);
+        assert_eq!(restored, 0x20, "R{id} + 0x10 - 0x10");
+
+        let mask = width_mask::<R>(register_type);
+        match registers.arithmetic_policy().overflow {
+            OverflowPolicy::Wrap => {
+                let wrapped = registers
+                    .write_to(register_type, mask)
+                    .add_to(register_type, 1)
+                    .read_from(register_type);
+                assert_eq!(wrapped, 0, "R{id} = {mask:#X} + 1 did not wrap");
+            }
+            OverflowPolicy::Saturate => {
+                let saturated = registers
+                    .write_to(register_type, mask)
+                    .add_to(register_type, 1)
+                    .read_from(register_type);
+                assert_eq!(saturated, mask, "R{id} = {mask:#X} + 1 did not saturate");
+
+                let floored = registers
+                    .write_to(register_type, 0)
+                    .sub_from(register_type, 1)
+                    .read_from(register_type);
+                assert_eq!(floored, 0, "R{id} = 0 - 1 did not saturate");
+            }
+        }
+    }
+}
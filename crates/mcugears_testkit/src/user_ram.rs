@@ -0,0 +1,100 @@
+// 要素import
+use mcugears_core::error::MemoryError;
+use mcugears_core::user_ram::{RamAddress, UserRam};
+use std::panic;
+
+// 確認に使うアドレス(先頭,中央,末尾)
+fn sample_addresses<R: UserRam>() -> [usize; 3] {
+    let middle = R::START_ADDRESS + (R::END_ADDRESS - R::START_ADDRESS) / 2;
+    [R::START_ADDRESS, middle, R::END_ADDRESS]
+}
+
+// アドレス範囲が正しい
+pub fn address_range<R: UserRam>() {
+    assert!(
+        R::START_ADDRESS <= R::END_ADDRESS,
+        "START_ADDRESS {:#06X} is after END_ADDRESS {:#06X}",
+        R::START_ADDRESS,
+        R::END_ADDRESS
+    );
+}
+
+// 読み書きの往復で値が保たれ、幅を超えた分は切り捨てられる
+pub fn round_trip<R: UserRam>() {
+    for address in sample_addresses::<R>().map(RamAddress) {
+        let mask = R::new().write_to(address, usize::MAX).read_from(address);
+        assert_ne!(mask, 0, "{address} cannot hold any bit");
+
+        for value in [0, 1, 0x5A, mask, mask.wrapping_add(1).wrapping_add(0x5A)] {
+            let result = R::new().write_to(address, value).read_from(address);
+            assert_eq!(
+                result,
+                value & mask,
+                "{address} read back {result:#X} after writing {value:#X}"
+            );
+        }
+    }
+}
+
+// 書き込みは隣のアドレスに影響しない
+pub fn isolation<R: UserRam>() {
+    for address in sample_addresses::<R>() {
+        let mut user_ram = R::new();
+        let neighbours: Vec<usize> = [address.checked_sub(1), address.checked_add(1)]
+            .into_iter()
+            .flatten()
+            .filter(|neighbour| (R::START_ADDRESS..=R::END_ADDRESS).contains(neighbour))
+            .collect();
+        let before: Vec<usize> = neighbours
+            .iter()
+            .map(|neighbour| user_ram.read_from(RamAddress(*neighbour)))
+            .collect();
+
+        user_ram.write_to(RamAddress(address), usize::MAX);
+
+        for (neighbour, before) in neighbours.into_iter().zip(before) {
+            assert_eq!(
+                user_ram.read_from(RamAddress(neighbour)),
+                before,
+                "writing {} changed {}",
+                RamAddress(address),
+                RamAddress(neighbour)
+            );
+        }
+    }
+}
+
+// 範囲外のアドレスはtry_*でエラーになる
+pub fn boundary<R: UserRam>() {
+    let mut user_ram = R::new();
+    let outside = [
+        R::START_ADDRESS.checked_sub(1),
+        R::END_ADDRESS.checked_add(1),
+    ];
+
+    for address in outside.into_iter().flatten().map(RamAddress) {
+        let expected = Err(MemoryError::OutOfRange { address });
+        assert_eq!(user_ram.try_write_to(address, 1).map(|_| ()), expected);
+        assert_eq!(user_ram.try_read_from(address).map(|_| ()), expected);
+    }
+}
+
+// 範囲外のアドレスへの読み書きはpanicする
+pub fn out_of_range_panics<R: UserRam>() {
+    let outside = [
+        R::START_ADDRESS.checked_sub(1),
+        R::END_ADDRESS.checked_add(1),
+    ];
+
+    for address in outside.into_iter().flatten().map(RamAddress) {
+        let write = panic::catch_unwind(|| {
+            R::new().write_to(address, 1);
+        });
+        assert!(write.is_err(), "writing {address} did not panic");
+
+        let read = panic::catch_unwind(|| {
+            R::new().read_from(address);
+        });
+        assert!(read.is_err(), "reading {address} did not panic");
+    }
+}
@@ -0,0 +1,27 @@
+// テスト共通のレジスタ構成
+use mcugears_core::generic_registers::{FlagLayout, GenericRegisters};
+use mcugears_core::registers::Flag;
+
+// AVRと同じフラグ配置
+#[derive(Clone, Debug, PartialEq)]
+pub struct AvrLayout;
+
+impl FlagLayout for AvrLayout {
+    fn flag_bit(flag: Flag) -> Option<usize> {
+        match flag {
+            Flag::Carry => Some(0),
+            Flag::Zero => Some(1),
+            Flag::Negative => Some(2),
+            Flag::Overflow => Some(3),
+            Flag::Sign => Some(4),
+            Flag::HalfCarry => Some(5),
+            Flag::BitCopy => Some(6),
+            Flag::InterruptEnable => Some(7),
+        }
+    }
+}
+
+// 8bitレジスタ,16bitポインタ
+pub type AvrRegisters = GenericRegisters<32, 224, u8, u16, AvrLayout>;
+// 32bitレジスタ,32bitポインタ,IOレジスタ無し
+pub type WideRegisters = GenericRegisters<16, 0, u32, u32, AvrLayout>;
@@ -0,0 +1,93 @@
+// テストキット自体のテスト
+mod common;
+
+use common::{AvrRegisters, WideRegisters};
+use core::convert::Infallible;
+use mcugears_core::array_ram::ArrayRam;
+use mcugears_core::generic_registers::{FlagLayout, GenericRegisters};
+use mcugears_core::registers::{ArithmeticPolicy, Flag, OverflowPolicy, RegisterType, Registers};
+use mcugears_derive::Registers;
+
+// utility
+// 同じビットに2つのフラグを割り当てた誤った配置
+#[derive(Clone, Debug, PartialEq)]
+struct OverlappingLayout;
+
+impl FlagLayout for OverlappingLayout {
+    fn flag_bit(flag: Flag) -> Option<usize> {
+        match flag {
+            Flag::Carry | Flag::Zero => Some(0),
+            _ => None,
+        }
+    }
+}
+
+// deriveしたレジスタ構造体
+#[derive(Registers)]
+#[flags(Carry = 0, Zero = 1)]
+struct DerivedRegisters {
+    #[register(general)]
+    general: [u16; 16],
+    #[register(status)]
+    status: u8,
+    #[register(stack_pointer)]
+    stack_pointer: u32,
+    #[register(program_counter)]
+    program_counter: u32,
+    #[register(io)]
+    io: [u8; 4],
+}
+
+type AvrSram = ArrayRam<0x0800, 0x0100>;
+type ZeroPageRam = ArrayRam<0x0100, 0x0000>;
+
+// 飽和ポリシーで初期化するレジスタ
+struct SaturatingRegisters(AvrRegisters);
+
+impl Registers for SaturatingRegisters {
+    type Extended = Infallible;
+    const GENERAL_COUNT: usize = AvrRegisters::GENERAL_COUNT;
+    const IO_COUNT: usize = AvrRegisters::IO_COUNT;
+
+    fn new() -> Self {
+        let policy = ArithmeticPolicy {
+            overflow: OverflowPolicy::Saturate,
+            ..Default::default()
+        };
+        SaturatingRegisters(AvrRegisters::new().with_arithmetic_policy(policy))
+    }
+
+    fn write_to(&mut self, register_type: RegisterType, value: usize) -> &mut Self {
+        self.0.write_to(register_type, value);
+        self
+    }
+
+    fn read_from(&self, register_type: RegisterType) -> usize {
+        self.0.read_from(register_type)
+    }
+
+    fn flag_bit(flag: Flag) -> Option<usize> {
+        AvrRegisters::flag_bit(flag)
+    }
+
+    fn arithmetic_policy(&self) -> ArithmeticPolicy {
+        self.0.arithmetic_policy()
+    }
+}
+
+// 適合性テスト
+mcugears_testkit::registers_conformance!(avr_registers, AvrRegisters);
+mcugears_testkit::registers_conformance!(wide_registers, WideRegisters);
+mcugears_testkit::registers_conformance!(derived_registers, DerivedRegisters);
+mcugears_testkit::registers_conformance!(saturating_registers, SaturatingRegisters);
+mcugears_testkit::user_ram_conformance!(avr_sram, AvrSram);
+mcugears_testkit::user_ram_conformance!(zero_page_ram, ZeroPageRam);
+
+// 誤ったフラグ配置を検出できる
+#[test]
+#[should_panic]
+fn detects_overlapping_flags() {
+    mcugears_testkit::registers::flag_truth_table::<
+        GenericRegisters<32, 64, u8, u16, OverlappingLayout>,
+    >();
+}
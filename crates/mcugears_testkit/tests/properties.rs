@@ -0,0 +1,68 @@
+// 性質テスト
+mod common;
+
+use common::{AvrRegisters, WideRegisters};
+use mcugears_core::generic_registers::{FlagLayout, GenericRegisters};
+use mcugears_core::registers::Flag;
+use mcugears_testkit::properties::*;
+use proptest::prelude::*;
+
+// utility
+// フラグを持たない配置
+#[derive(Clone, Debug, PartialEq)]
+struct NoFlagLayout;
+
+impl FlagLayout for NoFlagLayout {
+    fn flag_bit(_flag: Flag) -> Option<usize> {
+        None
+    }
+}
+
+proptest! {
+    // 加算と減算は打ち消し合う
+    #[test]
+    fn add_sub(
+        registers in register_state::<AvrRegisters>(),
+        register_type in general_register::<AvrRegisters>(),
+        operand in any::<usize>(),
+    ) {
+        add_sub_restores(registers, register_type, operand)?;
+    }
+
+    // 切り捨て
+    #[test]
+    fn truncation(
+        registers in register_state::<AvrRegisters>(),
+        register_type in register_type::<AvrRegisters>(),
+        value in any::<usize>(),
+    ) {
+        write_read_truncates(registers, register_type, value)?;
+    }
+
+    // IOレジスタの無いアーキテクチャでの切り捨て
+    #[test]
+    fn wide_truncation(
+        registers in register_state::<WideRegisters>(),
+        register_type in register_type::<WideRegisters>(),
+        value in any::<usize>(),
+    ) {
+        write_read_truncates(registers, register_type, value)?;
+    }
+
+    // フラグの参照モデル
+    #[test]
+    fn flags(
+        registers in register_state::<AvrRegisters>(),
+        flag in flag::<AvrRegisters>(),
+        value in any::<bool>(),
+    ) {
+        flags_match_reference(registers, flag, value)?;
+    }
+}
+
+// フラグの無いアーキテクチャではflag strategyを作れない
+#[test]
+#[should_panic(expected = "maps no flags")]
+fn flag_strategy_without_flags() {
+    let _ = flag::<GenericRegisters<16, 0, u32, u32, NoFlagLayout>>();
+}